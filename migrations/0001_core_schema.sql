@@ -0,0 +1,78 @@
+-- Accounts, businesses, products and drones
+
+CREATE TABLE users (
+    id INT NOT NULL AUTO_INCREMENT,
+    username VARCHAR(50) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    lastname VARCHAR(100) NOT NULL,
+    phone BIGINT NOT NULL,
+    email VARCHAR(255) NULL,
+    password_hash VARCHAR(255) NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id),
+    UNIQUE KEY uq_users_username (username)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE admins (
+    user_id INT NOT NULL,
+    PRIMARY KEY (user_id),
+    CONSTRAINT fk_admins_user FOREIGN KEY (user_id) REFERENCES users (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE locations (
+    id INT NOT NULL AUTO_INCREMENT,
+    name VARCHAR(255) NULL,
+    latitude DECIMAL(10, 7) NOT NULL,
+    longitude DECIMAL(10, 7) NOT NULL,
+    PRIMARY KEY (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE businesses (
+    id INT NOT NULL AUTO_INCREMENT,
+    name VARCHAR(100) NOT NULL,
+    description TEXT NULL,
+    owner_id INT NOT NULL,
+    location_id INT NULL,
+    verified BOOLEAN NOT NULL DEFAULT FALSE,
+    verification_status ENUM('Pending', 'Verified', 'Rejected') NOT NULL DEFAULT 'Pending',
+    rejection_reason TEXT NULL,
+    reviewed_by INT NULL,
+    reviewed_at DATETIME NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id),
+    KEY idx_businesses_owner (owner_id),
+    KEY idx_businesses_verification (verification_status, created_at),
+    CONSTRAINT fk_businesses_owner FOREIGN KEY (owner_id) REFERENCES users (id),
+    CONSTRAINT fk_businesses_location FOREIGN KEY (location_id) REFERENCES locations (id),
+    CONSTRAINT fk_businesses_reviewer FOREIGN KEY (reviewed_by) REFERENCES users (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE products (
+    id INT NOT NULL AUTO_INCREMENT,
+    name VARCHAR(100) NOT NULL,
+    description TEXT NULL,
+    price DECIMAL(10, 2) NOT NULL,
+    business_id INT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id),
+    KEY idx_products_business (business_id, created_at),
+    CONSTRAINT fk_products_business FOREIGN KEY (business_id) REFERENCES businesses (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE drones (
+    id INT NOT NULL AUTO_INCREMENT,
+    name VARCHAR(100) NOT NULL,
+    number INT NOT NULL,
+    -- km/h
+    cruise_speed DOUBLE NOT NULL DEFAULT 40,
+    user_id INT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id),
+    UNIQUE KEY uq_drones_number (number),
+    KEY idx_drones_user (user_id, created_at),
+    CONSTRAINT fk_drones_user FOREIGN KEY (user_id) REFERENCES users (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
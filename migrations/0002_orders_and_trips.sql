@@ -0,0 +1,88 @@
+-- Orders, trips and the telemetry drones send while flying
+
+-- Single row counter behind handlers::flight_number
+CREATE TABLE flight_number_sequence (
+    id INT NOT NULL,
+    next_value BIGINT UNSIGNED NOT NULL DEFAULT 0,
+    PRIMARY KEY (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+INSERT INTO flight_number_sequence (id, next_value) VALUES (1, 0);
+
+CREATE TABLE orders (
+    id INT NOT NULL AUTO_INCREMENT,
+    flight_number VARCHAR(10) NOT NULL,
+    date DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    state ENUM('Requested', 'Finished', 'Canceled', 'Uncomplete') NOT NULL DEFAULT 'Requested',
+    total_price DECIMAL(10, 2) NOT NULL,
+    approved BOOLEAN NOT NULL DEFAULT FALSE,
+    user_id INT NOT NULL,
+    PRIMARY KEY (id),
+    UNIQUE KEY uq_orders_flight_number (flight_number),
+    KEY idx_orders_user (user_id, date),
+    CONSTRAINT fk_orders_user FOREIGN KEY (user_id) REFERENCES users (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE order_details (
+    id INT NOT NULL AUTO_INCREMENT,
+    order_id INT NOT NULL,
+    product_id INT NOT NULL,
+    -- Name and unit price of the product when the order was placed
+    product_name VARCHAR(100) NOT NULL,
+    amount INT NOT NULL,
+    price DECIMAL(10, 2) NOT NULL,
+    PRIMARY KEY (id),
+    KEY idx_order_details_order (order_id),
+    KEY idx_order_details_product (product_id),
+    CONSTRAINT fk_order_details_order FOREIGN KEY (order_id) REFERENCES orders (id),
+    CONSTRAINT fk_order_details_product FOREIGN KEY (product_id) REFERENCES products (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+-- Distances are in km and times in minutes, battery levels in %
+CREATE TABLE trips (
+    id INT NOT NULL AUTO_INCREMENT,
+    request_time DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    weight DOUBLE NOT NULL,
+    distance DOUBLE NOT NULL,
+    est_time DOUBLE NOT NULL,
+    state ENUM('Requested', 'Delivered', 'Finished', 'Canceled', 'Unfinished') NOT NULL
+        DEFAULT 'Requested',
+    packing_time DOUBLE NULL,
+    battery_init DOUBLE NULL,
+    arrival_time DOUBLE NULL,
+    delivery_time DOUBLE NULL,
+    real_distance DOUBLE NULL,
+    battery_arrival DOUBLE NULL,
+    round_trip_time DOUBLE NULL,
+    total_time DOUBLE NULL,
+    battery_end DOUBLE NULL,
+    canceled_time DATETIME NULL,
+    unfinished_time DATETIME NULL,
+    order_id INT NOT NULL,
+    from_location_id INT NOT NULL,
+    to_location_id INT NOT NULL,
+    drone_id INT NOT NULL,
+    PRIMARY KEY (id),
+    KEY idx_trips_order (order_id, state),
+    KEY idx_trips_drone (drone_id, request_time),
+    KEY idx_trips_request_time (request_time),
+    CONSTRAINT fk_trips_order FOREIGN KEY (order_id) REFERENCES orders (id),
+    CONSTRAINT fk_trips_from_location FOREIGN KEY (from_location_id) REFERENCES locations (id),
+    CONSTRAINT fk_trips_to_location FOREIGN KEY (to_location_id) REFERENCES locations (id),
+    CONSTRAINT fk_trips_drone FOREIGN KEY (drone_id) REFERENCES drones (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE trip_telemetry (
+    id INT NOT NULL AUTO_INCREMENT,
+    trip_id INT NOT NULL,
+    latitude DECIMAL(10, 7) NOT NULL,
+    longitude DECIMAL(10, 7) NOT NULL,
+    -- Meters above ground
+    altitude DOUBLE NOT NULL,
+    battery DOUBLE NOT NULL,
+    recorded_at DATETIME NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id),
+    KEY idx_trip_telemetry_trip (trip_id, recorded_at),
+    CONSTRAINT fk_trip_telemetry_trip FOREIGN KEY (trip_id) REFERENCES trips (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
@@ -0,0 +1,15 @@
+-- Areas drones can't fly over while the zone is active
+
+CREATE TABLE prohibited_zones (
+    id INT NOT NULL AUTO_INCREMENT,
+    description VARCHAR(255) NOT NULL,
+    since DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- NULL means the zone stays active indefinitely
+    `to` DATETIME NULL,
+    -- km
+    radius DOUBLE NOT NULL,
+    location_id INT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id),
+    CONSTRAINT fk_prohibited_zones_location FOREIGN KEY (location_id) REFERENCES locations (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
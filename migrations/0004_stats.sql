@@ -0,0 +1,44 @@
+-- Global stats row, daily buckets for the stats series and per-user counters
+
+CREATE TABLE stats (
+    id INT NOT NULL,
+    total_trips INT NOT NULL DEFAULT 0,
+    avg_delivery_time DOUBLE NOT NULL DEFAULT 0,
+    avg_packing_time DOUBLE NOT NULL DEFAULT 0,
+    avg_battery_consumption_per_km DOUBLE NOT NULL DEFAULT 0,
+    cancellation_rate DOUBLE NOT NULL DEFAULT 0,
+    active_accounts INT NOT NULL DEFAULT 0,
+    inactive_accounts INT NOT NULL DEFAULT 0,
+    total_accounts INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+-- Every handler updates the row with id = 1
+INSERT INTO stats (id) VALUES (1);
+
+-- Buckets keep sums and counts instead of averages, so they can be merged into weeks and months
+CREATE TABLE daily_stats (
+    day DATE NOT NULL,
+    trips INT NOT NULL DEFAULT 0,
+    finished_trips INT NOT NULL DEFAULT 0,
+    canceled_trips INT NOT NULL DEFAULT 0,
+    unfinished_trips INT NOT NULL DEFAULT 0,
+    total_delivery_time DOUBLE NOT NULL DEFAULT 0,
+    delivery_count INT NOT NULL DEFAULT 0,
+    total_packing_time DOUBLE NOT NULL DEFAULT 0,
+    packing_count INT NOT NULL DEFAULT 0,
+    battery_used DOUBLE NOT NULL DEFAULT 0,
+    battery_distance DOUBLE NOT NULL DEFAULT 0,
+    new_accounts INT NOT NULL DEFAULT 0,
+    deactivated_accounts INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (day)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE user_stats (
+    user_id INT NOT NULL,
+    trips INT NOT NULL DEFAULT 0,
+    canceled_trips INT NOT NULL DEFAULT 0,
+    last_login DATETIME NULL,
+    PRIMARY KEY (user_id),
+    CONSTRAINT fk_user_stats_user FOREIGN KEY (user_id) REFERENCES users (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
@@ -0,0 +1,49 @@
+-- Reports filed by users, each one linked to its target through one of the report_* tables
+
+CREATE TABLE reports (
+    id INT NOT NULL AUTO_INCREMENT,
+    title VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL,
+    reporter_id INT NOT NULL,
+    state ENUM('Open', 'Resolved', 'Dismissed') NOT NULL DEFAULT 'Open',
+    resolution TEXT NULL,
+    resolved_by INT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved_at DATETIME NULL,
+    PRIMARY KEY (id),
+    KEY idx_reports_state (state, created_at),
+    CONSTRAINT fk_reports_reporter FOREIGN KEY (reporter_id) REFERENCES users (id),
+    CONSTRAINT fk_reports_resolver FOREIGN KEY (resolved_by) REFERENCES users (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE report_trips (
+    report_id INT NOT NULL,
+    trip_id INT NOT NULL,
+    PRIMARY KEY (report_id),
+    CONSTRAINT fk_report_trips_report FOREIGN KEY (report_id) REFERENCES reports (id),
+    CONSTRAINT fk_report_trips_trip FOREIGN KEY (trip_id) REFERENCES trips (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE report_users (
+    report_id INT NOT NULL,
+    user_id INT NOT NULL,
+    PRIMARY KEY (report_id),
+    CONSTRAINT fk_report_users_report FOREIGN KEY (report_id) REFERENCES reports (id),
+    CONSTRAINT fk_report_users_user FOREIGN KEY (user_id) REFERENCES users (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE report_businesses (
+    report_id INT NOT NULL,
+    business_id INT NOT NULL,
+    PRIMARY KEY (report_id),
+    CONSTRAINT fk_report_businesses_report FOREIGN KEY (report_id) REFERENCES reports (id),
+    CONSTRAINT fk_report_businesses_business FOREIGN KEY (business_id) REFERENCES businesses (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE report_products (
+    report_id INT NOT NULL,
+    product_id INT NOT NULL,
+    PRIMARY KEY (report_id),
+    CONSTRAINT fk_report_products_report FOREIGN KEY (report_id) REFERENCES reports (id),
+    CONSTRAINT fk_report_products_product FOREIGN KEY (product_id) REFERENCES products (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
@@ -0,0 +1,11 @@
+-- Users working for a business, who share its product and order management with the owner
+
+CREATE TABLE business_employees (
+    business_id INT NOT NULL,
+    user_id INT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (business_id, user_id),
+    KEY idx_business_employees_user (user_id),
+    CONSTRAINT fk_business_employees_business FOREIGN KEY (business_id) REFERENCES businesses (id),
+    CONSTRAINT fk_business_employees_user FOREIGN KEY (user_id) REFERENCES users (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
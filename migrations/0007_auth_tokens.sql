@@ -0,0 +1,21 @@
+-- Refresh tokens and the access token denylist, all timestamps are UTC
+
+CREATE TABLE refresh_tokens (
+    id INT NOT NULL AUTO_INCREMENT,
+    user_id INT NOT NULL,
+    -- SHA-256 of the token, the token itself is never stored
+    token_hash CHAR(64) NOT NULL,
+    expires_at DATETIME NOT NULL,
+    revoked_at DATETIME NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (id),
+    UNIQUE KEY uq_refresh_tokens_hash (token_hash),
+    KEY idx_refresh_tokens_user (user_id),
+    CONSTRAINT fk_refresh_tokens_user FOREIGN KEY (user_id) REFERENCES users (id)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
+
+CREATE TABLE revoked_tokens (
+    jti VARCHAR(36) NOT NULL,
+    expires_at DATETIME NOT NULL,
+    PRIMARY KEY (jti)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4;
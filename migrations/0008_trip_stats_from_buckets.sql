@@ -0,0 +1,9 @@
+-- The trip counters and averages are computed from daily_stats when GET /stats runs, the
+-- stats row only keeps the account counters
+
+ALTER TABLE stats
+    DROP COLUMN total_trips,
+    DROP COLUMN avg_delivery_time,
+    DROP COLUMN avg_packing_time,
+    DROP COLUMN avg_battery_consumption_per_km,
+    DROP COLUMN cancellation_rate;
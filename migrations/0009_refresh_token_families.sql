@@ -0,0 +1,13 @@
+-- Refresh tokens rotated from the same login share a family, so reusing a rotated token only
+-- revokes the tokens of that session. revoked_reason tells a rotation apart from a logout.
+
+ALTER TABLE refresh_tokens
+    ADD COLUMN family_id CHAR(36) NULL AFTER token_hash,
+    ADD COLUMN revoked_reason ENUM('Rotated', 'Logout', 'Reused') NULL AFTER revoked_at,
+    ADD KEY idx_refresh_tokens_family (family_id);
+
+-- Existing tokens each get their own family. Their revocation reason is unknown, so reusing
+-- one of them never triggers the reuse detection
+UPDATE refresh_tokens SET family_id = UUID();
+
+ALTER TABLE refresh_tokens MODIFY family_id CHAR(36) NOT NULL;
@@ -1,9 +1,28 @@
 use std::time::Duration;
 
-use sqlx::mysql::{MySqlPool, MySqlPoolOptions};
+use sqlx::{
+    migrate::{MigrateError, Migrator},
+    mysql::{MySqlPool, MySqlPoolOptions},
+};
+
+/// Migrations in the `migrations` directory, embedded in the binary at compile time.
+static MIGRATOR: Migrator = sqlx::migrate!();
 
 /// Creates and returns a MySQL connection pool.
+///
+/// Pending migrations are applied first when `RUN_MIGRATIONS` is enabled.
 pub async fn create_pool() -> Result<MySqlPool, sqlx::Error> {
+    let pool = connect().await?;
+
+    if crate::config::get_run_migrations() {
+        run_migrations(&pool).await?;
+    }
+
+    Ok(pool)
+}
+
+/// Connects to the database without touching its schema.
+pub async fn connect() -> Result<MySqlPool, sqlx::Error> {
     // Load the database URL from the .env file.
     let database_url =
         std::env::var("DATABASE_URL").expect("DATABASE_URL must be defined in the .env file");
@@ -23,3 +42,11 @@ pub async fn create_pool() -> Result<MySqlPool, sqlx::Error> {
 
     Ok(pool)
 }
+
+/// Applies the migrations that haven't run yet, in version order.
+///
+/// Applied migrations are tracked in the `_sqlx_migrations` table, and a migration that was
+/// edited after being applied makes this fail instead of running it again.
+pub async fn run_migrations(pool: &MySqlPool) -> Result<(), MigrateError> {
+    MIGRATOR.run(pool).await
+}
@@ -12,11 +12,49 @@ pub fn get_server_addr() -> SocketAddr {
         .expect("SOCKET_ADDR must be a valid address (e.g., 127.0.0.1:3000)")
 }
 
+/// Returns whether pending migrations run when the pool is created (`RUN_MIGRATIONS`, off by default).
+pub fn get_run_migrations() -> bool {
+    std::env::var("RUN_MIGRATIONS")
+        .map(|value| value.parse().expect("RUN_MIGRATIONS must be true or false"))
+        .unwrap_or(false)
+}
+
 /// Returns the JWT secret key from the environment variables.
 pub fn get_jwt_secret() -> String {
     std::env::var("JWT_SECRET").expect("JWT_SECRET must be defined in the .env file")
 }
 
+/// Returns the JWT signing algorithm from the environment variables: HS256 (default), RS256 or EdDSA.
+pub fn get_jwt_algorithm() -> String {
+    std::env::var("JWT_ALGORITHM").unwrap_or_else(|_| "HS256".to_string())
+}
+
+/// Returns the key ID and the PEM private key path used to sign tokens (RS256 and EdDSA only).
+pub fn get_jwt_signing_key() -> (String, String) {
+    let kid = std::env::var("JWT_SIGNING_KEY_ID")
+        .expect("JWT_SIGNING_KEY_ID must be defined in the .env file");
+    let path = std::env::var("JWT_PRIVATE_KEY_PATH")
+        .expect("JWT_PRIVATE_KEY_PATH must be defined in the .env file");
+    (kid, path)
+}
+
+/// Returns the public keys accepted to verify tokens (RS256 and EdDSA only).
+///
+/// Format: `kid=path,kid=path`. Keep the previous key listed while rotating, until the
+/// tokens it signed expire.
+pub fn get_jwt_public_keys() -> Vec<(String, String)> {
+    std::env::var("JWT_PUBLIC_KEYS")
+        .expect("JWT_PUBLIC_KEYS must be defined in the .env file")
+        .split(',')
+        .map(|entry| {
+            let (kid, path) = entry
+                .split_once('=')
+                .expect("JWT_PUBLIC_KEYS must be a list of kid=path entries");
+            (kid.trim().to_string(), path.trim().to_string())
+        })
+        .collect()
+}
+
 /// Returns the JWT expiration time in hours from the environment variables.
 pub fn get_jwt_expiration_hours() -> i64 {
     std::env::var("JWT_EXPIRATION_HOURS")
@@ -24,3 +62,11 @@ pub fn get_jwt_expiration_hours() -> i64 {
         .parse()
         .expect("JWT_EXPIRATION_HOURS must be a valid number")
 }
+
+/// Returns the refresh token expiration time in days from the environment variables.
+pub fn get_refresh_token_expiration_days() -> i64 {
+    std::env::var("REFRESH_TOKEN_EXPIRATION_DAYS")
+        .expect("REFRESH_TOKEN_EXPIRATION_DAYS must be defined in the .env file")
+        .parse()
+        .expect("REFRESH_TOKEN_EXPIRATION_DAYS must be a valid number")
+}
@@ -0,0 +1,262 @@
+use axum::{
+    Json,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+use crate::middleware::{auth::AuthError, rbac::Role};
+
+/// Error body returned by every endpoint, following RFC 7807 (problem details)
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ProblemDetails {
+    /// URI identifying the problem type, "about:blank" when only `code` identifies it
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// Short summary of the HTTP status
+    pub title: String,
+    /// HTTP status code
+    pub status: u16,
+    /// Explanation specific to this occurrence of the problem
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Machine-readable error code, e.g. "product_inactive"
+    pub code: String,
+    /// ID of the prohibited zone crossed by the route, only for "route_blocked"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zone_id: Option<i32>,
+    /// Invalid fields of the request body, only for "validation_failed"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<FieldError>>,
+}
+
+/// A validation rule broken by a field of the request body
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct FieldError {
+    /// Path of the field, e.g. "order_details[0].amount"
+    pub field: String,
+    /// Rule that failed, e.g. "range" or "email"
+    pub code: String,
+    /// Human-readable explanation of the rule
+    pub message: String,
+}
+
+/// Error type returned by every handler
+///
+/// Each variant carries a machine-readable code and a human-readable detail, and is
+/// rendered as `application/problem+json`.
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(&'static str, String),
+    Unauthorized(&'static str, String),
+    Forbidden(&'static str, String),
+    NotFound(&'static str, String),
+    Conflict(&'static str, String),
+    /// The request body broke the validation rules of its model
+    Validation(Vec<FieldError>),
+    /// The route of a trip crosses an active prohibited zone
+    RouteBlocked {
+        zone_id: i32,
+        description: String,
+    },
+    /// Anything the client can't fix, the cause is logged instead of returned
+    Internal,
+}
+
+impl ApiError {
+    pub fn bad_request(code: &'static str, detail: impl Into<String>) -> Self {
+        ApiError::BadRequest(code, detail.into())
+    }
+
+    pub fn unauthorized(code: &'static str, detail: impl Into<String>) -> Self {
+        ApiError::Unauthorized(code, detail.into())
+    }
+
+    pub fn forbidden(code: &'static str, detail: impl Into<String>) -> Self {
+        ApiError::Forbidden(code, detail.into())
+    }
+
+    pub fn not_found(code: &'static str, detail: impl Into<String>) -> Self {
+        ApiError::NotFound(code, detail.into())
+    }
+
+    pub fn conflict(code: &'static str, detail: impl Into<String>) -> Self {
+        ApiError::Conflict(code, detail.into())
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest(..) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(..) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(..) => StatusCode::FORBIDDEN,
+            ApiError::NotFound(..) => StatusCode::NOT_FOUND,
+            ApiError::Conflict(..) => StatusCode::CONFLICT,
+            ApiError::Validation(_) => StatusCode::BAD_REQUEST,
+            ApiError::RouteBlocked { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Build the problem details body of the error
+    pub fn problem(&self) -> ProblemDetails {
+        let status = self.status();
+        let mut errors = None;
+        let (code, detail, zone_id) = match self {
+            ApiError::BadRequest(code, detail)
+            | ApiError::Unauthorized(code, detail)
+            | ApiError::Forbidden(code, detail)
+            | ApiError::NotFound(code, detail)
+            | ApiError::Conflict(code, detail) => (*code, Some(detail.clone()), None),
+            ApiError::Validation(field_errors) => {
+                errors = Some(field_errors.clone());
+                (
+                    "validation_failed",
+                    Some("The request has invalid fields".to_string()),
+                    None,
+                )
+            }
+            ApiError::RouteBlocked {
+                zone_id,
+                description,
+            } => (
+                "route_blocked",
+                Some(format!(
+                    "Route crosses prohibited zone {}: {}",
+                    zone_id, description
+                )),
+                Some(*zone_id),
+            ),
+            ApiError::Internal => ("internal_error", None, None),
+        };
+
+        ProblemDetails {
+            problem_type: "about:blank".to_string(),
+            title: status.canonical_reason().unwrap_or_default().to_string(),
+            status: status.as_u16(),
+            detail,
+            code: code.to_string(),
+            zone_id,
+            errors,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status(),
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            Json(self.problem()),
+        )
+            .into_response()
+    }
+}
+
+// Unique and foreign key violations are caused by the request, everything else is on us
+impl From<sqlx::Error> for ApiError {
+    fn from(err: sqlx::Error) -> Self {
+        match &err {
+            sqlx::Error::RowNotFound => ApiError::not_found("not_found", "Resource not found"),
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => ApiError::conflict(
+                "duplicate",
+                "A resource with the same unique value already exists",
+            ),
+            sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                ApiError::conflict(
+                    "reference_violation",
+                    "The request references a resource that doesn't exist or is still in use",
+                )
+            }
+            _ => {
+                eprintln!("Database error: {}", err);
+                ApiError::Internal
+            }
+        }
+    }
+}
+
+impl From<AuthError> for ApiError {
+    fn from(err: AuthError) -> Self {
+        match err {
+            AuthError::InvalidToken => ApiError::unauthorized("invalid_token", "Invalid token"),
+            AuthError::MissingToken => ApiError::unauthorized("missing_token", "Missing token"),
+            AuthError::TokenExpired => ApiError::unauthorized("token_expired", "Token expired"),
+            AuthError::MissingRole(roles) => {
+                let roles: Vec<&str> = roles.iter().map(Role::as_str).collect();
+                ApiError::forbidden(
+                    "missing_role",
+                    format!("Missing required role: {}", roles.join(" or ")),
+                )
+            }
+            AuthError::Internal => ApiError::Internal,
+        }
+    }
+}
+
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut field_errors = Vec::new();
+        collect_field_errors(&errors, "", &mut field_errors);
+        field_errors.sort_by(|a, b| a.field.cmp(&b.field));
+        ApiError::Validation(field_errors)
+    }
+}
+
+// Flatten nested errors into dotted paths, with the index of list items in brackets
+fn collect_field_errors(errors: &ValidationErrors, prefix: &str, out: &mut Vec<FieldError>) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                out.extend(field_errors.iter().map(|error| FieldError {
+                    field: path.clone(),
+                    code: error.code.to_string(),
+                    message: describe(error),
+                }));
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(nested, &path, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(nested, &format!("{}[{}]", path, index), out);
+                }
+            }
+        }
+    }
+}
+
+// Explain a failed rule, rules without a custom message are described from their params
+fn describe(error: &ValidationError) -> String {
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+
+    let param = |name: &str| error.params.get(name);
+    match error.code.as_ref() {
+        "email" => "must be a valid email address".to_string(),
+        "length" => match (param("min"), param("max")) {
+            (Some(min), Some(max)) => format!("length must be between {} and {}", min, max),
+            (Some(min), None) => format!("length must be at least {}", min),
+            (None, Some(max)) => format!("length must be at most {}", max),
+            (None, None) => "has an invalid length".to_string(),
+        },
+        "range" => {
+            let bounds: Vec<String> = [
+                ("min", "at least"),
+                ("exclusive_min", "greater than"),
+                ("max", "at most"),
+                ("exclusive_max", "less than"),
+            ]
+            .into_iter()
+            .filter_map(|(name, text)| param(name).map(|value| format!("{} {}", text, value)))
+            .collect();
+            format!("must be {}", bounds.join(" and "))
+        }
+        code => format!("failed the {} rule", code),
+    }
+}
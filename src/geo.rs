@@ -0,0 +1,207 @@
+/// Mean Earth radius in km
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Cruise speed in km/h used for drones that don't declare their own
+pub const DEFAULT_CRUISE_SPEED_KMH: f64 = 40.0;
+
+/// Fraction of cruise speed lost per kg of payload
+const SPEED_PENALTY_PER_KG: f64 = 0.05;
+
+/// A loaded drone never flies slower than this fraction of its cruise speed
+const MIN_SPEED_FACTOR: f64 = 0.5;
+
+/// A point on the Earth's surface in decimal degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+}
+
+/// Great-circle distance in km between two points, using the haversine formula
+pub fn haversine_distance(from: Coordinates, to: Coordinates) -> f64 {
+    let from_lat = from.latitude.to_radians();
+    let to_lat = to.latitude.to_radians();
+    let delta_lat = (to.latitude - from.latitude).to_radians();
+    let delta_lon = (to.longitude - from.longitude).to_radians();
+
+    let a = (delta_lat / 2.0).sin().powi(2)
+        + from_lat.cos() * to_lat.cos() * (delta_lon / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Estimated flight time in minutes for `distance` km carrying `weight` kg
+///
+/// Every kg of payload slows the drone down by `SPEED_PENALTY_PER_KG` of its cruise speed,
+/// never going below `MIN_SPEED_FACTOR`.
+pub fn estimate_flight_time(distance: f64, weight: f64, cruise_speed: f64) -> f64 {
+    let speed_factor = (1.0 - SPEED_PENALTY_PER_KG * weight).max(MIN_SPEED_FACTOR);
+    let speed = cruise_speed * speed_factor;
+
+    distance / speed * 60.0
+}
+
+/// Initial bearing in radians when flying from `from` towards `to`
+fn initial_bearing(from: Coordinates, to: Coordinates) -> f64 {
+    let from_lat = from.latitude.to_radians();
+    let to_lat = to.latitude.to_radians();
+    let delta_lon = (to.longitude - from.longitude).to_radians();
+
+    let y = delta_lon.sin() * to_lat.cos();
+    let x = from_lat.cos() * to_lat.sin() - from_lat.sin() * to_lat.cos() * delta_lon.cos();
+
+    y.atan2(x)
+}
+
+/// Shortest distance in km from `point` to the great-circle segment between `from` and `to`
+///
+/// Uses the cross-track distance when the closest point falls inside the segment, and the
+/// distance to the nearest endpoint otherwise.
+pub fn distance_to_segment(point: Coordinates, from: Coordinates, to: Coordinates) -> f64 {
+    let segment_length = haversine_distance(from, to);
+    let distance_from_start = haversine_distance(from, point);
+
+    if segment_length == 0.0 {
+        return distance_from_start;
+    }
+
+    let angular_from_start = distance_from_start / EARTH_RADIUS_KM;
+    let bearing_delta = initial_bearing(from, point) - initial_bearing(from, to);
+
+    // The point lies behind the start of the segment
+    if bearing_delta.cos() < 0.0 {
+        return distance_from_start;
+    }
+
+    let angular_cross_track = (angular_from_start.sin() * bearing_delta.sin()).asin();
+    let along_track = (angular_from_start.cos() / angular_cross_track.cos())
+        .clamp(-1.0, 1.0)
+        .acos()
+        * EARTH_RADIUS_KM;
+
+    // The point lies beyond the end of the segment
+    if along_track > segment_length {
+        return haversine_distance(to, point);
+    }
+
+    angular_cross_track.abs() * EARTH_RADIUS_KM
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUENOS_AIRES: Coordinates = Coordinates {
+        latitude: -34.6037,
+        longitude: -58.3816,
+    };
+    const MONTEVIDEO: Coordinates = Coordinates {
+        latitude: -34.9011,
+        longitude: -56.1645,
+    };
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn measures_a_known_city_pair() {
+        // About 205 km across the Río de la Plata
+        let distance = haversine_distance(BUENOS_AIRES, MONTEVIDEO);
+        assert!((204.0..206.5).contains(&distance), "got {}", distance);
+        assert_close(haversine_distance(MONTEVIDEO, BUENOS_AIRES), distance);
+    }
+
+    #[test]
+    fn same_point_is_zero_distance() {
+        assert_eq!(haversine_distance(BUENOS_AIRES, BUENOS_AIRES), 0.0);
+        assert_eq!(
+            estimate_flight_time(0.0, 3.0, DEFAULT_CRUISE_SPEED_KMH),
+            0.0
+        );
+    }
+
+    #[test]
+    fn payload_slows_the_drone_down() {
+        // 40 km at 40 km/h is an hour without payload
+        assert_close(estimate_flight_time(40.0, 0.0, 40.0), 60.0);
+        // 2 kg take 10% of the speed away
+        assert_close(estimate_flight_time(40.0, 2.0, 40.0), 60.0 / 0.9);
+    }
+
+    #[test]
+    fn heavy_payloads_stop_at_the_minimum_speed() {
+        // At 10 kg the penalty reaches MIN_SPEED_FACTOR, heavier payloads don't slow down more
+        let slowest = 60.0 / MIN_SPEED_FACTOR;
+        assert_close(estimate_flight_time(40.0, 10.0, 40.0), slowest);
+        assert_close(estimate_flight_time(40.0, 50.0, 40.0), slowest);
+    }
+
+    /// A segment of 1 degree along the equator, about 111 km
+    const SEGMENT_START: Coordinates = Coordinates {
+        latitude: 0.0,
+        longitude: 0.0,
+    };
+    const SEGMENT_END: Coordinates = Coordinates {
+        latitude: 0.0,
+        longitude: 1.0,
+    };
+
+    #[test]
+    fn point_beside_the_segment_uses_the_cross_track_distance() {
+        // 0.1 degrees north of the middle of the segment
+        let point = Coordinates::new(0.1, 0.5);
+        let expected = 0.1_f64.to_radians() * EARTH_RADIUS_KM;
+
+        assert_close(
+            distance_to_segment(point, SEGMENT_START, SEGMENT_END),
+            expected,
+        );
+        // Much closer than either endpoint
+        assert!(expected < haversine_distance(point, SEGMENT_START) / 4.0);
+    }
+
+    #[test]
+    fn point_before_the_start_clamps_to_the_start() {
+        for point in [Coordinates::new(0.0, -0.5), Coordinates::new(0.1, -0.5)] {
+            assert_close(
+                distance_to_segment(point, SEGMENT_START, SEGMENT_END),
+                haversine_distance(SEGMENT_START, point),
+            );
+        }
+    }
+
+    #[test]
+    fn point_past_the_end_clamps_to_the_end() {
+        for point in [Coordinates::new(0.0, 1.5), Coordinates::new(-0.1, 1.5)] {
+            assert_close(
+                distance_to_segment(point, SEGMENT_START, SEGMENT_END),
+                haversine_distance(SEGMENT_END, point),
+            );
+        }
+    }
+
+    #[test]
+    fn zero_length_segment_is_a_point() {
+        let point = Coordinates::new(0.1, 0.1);
+
+        assert_close(
+            distance_to_segment(point, SEGMENT_START, SEGMENT_START),
+            haversine_distance(SEGMENT_START, point),
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use sqlx::MySqlPool;
+
+/// How a user relates to a business
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusinessRole {
+    Owner,
+    Employee,
+    /// Neither owns nor works for the business
+    Outsider,
+}
+
+impl BusinessRole {
+    /// Owners and employees manage products and view orders,
+    /// only owners delete the business or manage its staff
+    pub fn is_staff(&self) -> bool {
+        matches!(self, BusinessRole::Owner | BusinessRole::Employee)
+    }
+}
+
+/// Resolve how a user relates to a business, None if the business doesn't exist
+pub async fn fetch_business_role(
+    pool: &MySqlPool,
+    business_id: i32,
+    user_id: i32,
+) -> Result<Option<BusinessRole>, sqlx::Error> {
+    let business = sqlx::query!(
+        r#"
+        SELECT
+            b.owner_id,
+            EXISTS(
+                SELECT 1 FROM business_employees WHERE business_id = b.id AND user_id = ?
+            ) as is_employee
+        FROM businesses b
+        WHERE b.id = ?
+        "#,
+        user_id,
+        business_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(business.map(|business| {
+        if business.owner_id == user_id {
+            BusinessRole::Owner
+        } else if business.is_employee != 0 {
+            BusinessRole::Employee
+        } else {
+            BusinessRole::Outsider
+        }
+    }))
+}
@@ -0,0 +1,74 @@
+use sqlx::MySqlConnection;
+
+/// Reserve the next flight number from the `flight_number_sequence` row
+///
+/// The counter is bumped with `LAST_INSERT_ID(expr)`, so the row stays locked until the
+/// surrounding transaction ends and concurrent orders always get different values. If the
+/// transaction is rolled back, the number is released with it.
+pub async fn next_flight_number(conn: &mut MySqlConnection) -> Result<String, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE flight_number_sequence SET next_value = LAST_INSERT_ID(next_value + 1) WHERE id = \
+         1"
+    )
+    .execute(conn)
+    .await?;
+
+    // The row is seeded by the migrations, without it no order can be placed
+    if result.rows_affected() == 0 {
+        return Err(sqlx::Error::Protocol(
+            "flight_number_sequence has no row with id = 1".to_string(),
+        ));
+    }
+
+    Ok(format_flight_number(result.last_insert_id()))
+}
+
+/// Format a sequence value as `AA999`
+///
+/// Every value gets its own number, so the format never wraps around: once the 676,000 numbers
+/// from `AA000` to `ZZ999` are used up, the prefix grows a letter (`AAA000`, ..., `ZZZ999`,
+/// `AAAA000`, ...).
+fn format_flight_number(value: u64) -> String {
+    // Find the prefix length, each extra letter adds 26 times as many prefixes
+    let mut prefix = value / 1000;
+    let mut letters = 2;
+    let mut prefixes: u64 = 26 * 26;
+    while prefix >= prefixes {
+        prefix -= prefixes;
+        letters += 1;
+        prefixes *= 26;
+    }
+
+    // Write the prefix in base 26, from the last letter to the first
+    let mut code = vec!['A'; letters];
+    for letter in code.iter_mut().rev() {
+        *letter = (b'A' + (prefix % 26) as u8) as char;
+        prefix /= 26;
+    }
+
+    let code: String = code.into_iter().collect();
+    format!("{}{:03}", code, value % 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_flight_number;
+
+    #[test]
+    fn formats_two_letter_numbers() {
+        assert_eq!(format_flight_number(0), "AA000");
+        assert_eq!(format_flight_number(999), "AA999");
+        assert_eq!(format_flight_number(1000), "AB000");
+        assert_eq!(format_flight_number(25_999), "AZ999");
+        assert_eq!(format_flight_number(26_000), "BA000");
+        assert_eq!(format_flight_number(675_999), "ZZ999");
+    }
+
+    #[test]
+    fn grows_a_letter_instead_of_wrapping_around() {
+        assert_eq!(format_flight_number(676_000), "AAA000");
+        assert_eq!(format_flight_number(676_001), "AAA001");
+        assert_eq!(format_flight_number(676_000 + 17_576_000 - 1), "ZZZ999");
+        assert_eq!(format_flight_number(676_000 + 17_576_000), "AAAA000");
+    }
+}
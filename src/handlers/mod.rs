@@ -1 +1,9 @@
+pub mod businesses;
+pub mod flight_number;
+pub mod orders;
+pub mod reports;
+pub mod roles;
 pub mod stats;
+pub mod tokens;
+pub mod trips;
+pub mod zones;
@@ -0,0 +1,27 @@
+use sqlx::MySqlConnection;
+
+use crate::models::order::OrderState;
+
+/// Move the order linked to a trip to `state`, keeping it consistent with the trip
+///
+/// Orders that already left the 'Requested' state are not touched.
+pub async fn sync_order_with_trip(
+    conn: &mut MySqlConnection,
+    trip_id: i32,
+    state: OrderState,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE orders o
+        JOIN trips t ON t.order_id = o.id
+        SET o.state = ?
+        WHERE t.id = ? AND o.state = 'Requested'
+        "#,
+        state.as_str(),
+        trip_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
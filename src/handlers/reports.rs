@@ -0,0 +1,215 @@
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::models::report::{ModerationAction, Report, ReportTarget};
+
+/// Whether the entity a report points to exists
+pub async fn report_target_exists(
+    conn: &mut MySqlConnection,
+    target: ReportTarget,
+    target_id: i32,
+) -> Result<bool, sqlx::Error> {
+    let exists = match target {
+        ReportTarget::Trip => {
+            sqlx::query_scalar!("SELECT EXISTS(SELECT 1 FROM trips WHERE id = ?)", target_id)
+                .fetch_one(&mut *conn)
+                .await?
+        }
+        ReportTarget::User => {
+            sqlx::query_scalar!("SELECT EXISTS(SELECT 1 FROM users WHERE id = ?)", target_id)
+                .fetch_one(&mut *conn)
+                .await?
+        }
+        ReportTarget::Business => {
+            sqlx::query_scalar!(
+                "SELECT EXISTS(SELECT 1 FROM businesses WHERE id = ?)",
+                target_id
+            )
+            .fetch_one(&mut *conn)
+            .await?
+        }
+        ReportTarget::Product => {
+            sqlx::query_scalar!(
+                "SELECT EXISTS(SELECT 1 FROM products WHERE id = ?)",
+                target_id
+            )
+            .fetch_one(&mut *conn)
+            .await?
+        }
+    };
+
+    Ok(exists != 0)
+}
+
+/// Link a report to its target through the target's link table
+pub async fn link_report(
+    conn: &mut MySqlConnection,
+    report_id: i32,
+    target: ReportTarget,
+    target_id: i32,
+) -> Result<(), sqlx::Error> {
+    match target {
+        ReportTarget::Trip => {
+            sqlx::query!(
+                "INSERT INTO report_trips (report_id, trip_id) VALUES (?, ?)",
+                report_id,
+                target_id
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        ReportTarget::User => {
+            sqlx::query!(
+                "INSERT INTO report_users (report_id, user_id) VALUES (?, ?)",
+                report_id,
+                target_id
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        ReportTarget::Business => {
+            sqlx::query!(
+                "INSERT INTO report_businesses (report_id, business_id) VALUES (?, ?)",
+                report_id,
+                target_id
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        ReportTarget::Product => {
+            sqlx::query!(
+                "INSERT INTO report_products (report_id, product_id) VALUES (?, ?)",
+                report_id,
+                target_id
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetch a report, resolving its target from the link tables
+///
+/// Locks the report row so concurrent resolutions are serialized.
+pub async fn fetch_report_for_update(
+    conn: &mut MySqlConnection,
+    report_id: i32,
+) -> Result<Option<Report>, sqlx::Error> {
+    sqlx::query_as!(
+        Report,
+        r#"
+        SELECT
+            r.id,
+            r.title,
+            r.description,
+            r.reporter_id,
+            r.state,
+            CASE
+                WHEN rt.trip_id IS NOT NULL THEN 'Trip'
+                WHEN ru.user_id IS NOT NULL THEN 'User'
+                WHEN rb.business_id IS NOT NULL THEN 'Business'
+                ELSE 'Product'
+            END as "target!: String",
+            COALESCE(rt.trip_id, ru.user_id, rb.business_id, rp.product_id) as "target_id!: i32",
+            r.resolution,
+            r.resolved_by,
+            r.created_at,
+            r.resolved_at
+        FROM reports r
+        LEFT JOIN report_trips rt ON rt.report_id = r.id
+        LEFT JOIN report_users ru ON ru.report_id = r.id
+        LEFT JOIN report_businesses rb ON rb.report_id = r.id
+        LEFT JOIN report_products rp ON rp.report_id = r.id
+        WHERE r.id = ?
+        FOR UPDATE
+        "#,
+        report_id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+}
+
+/// Fetch the reports in a state, optionally only those against one type of entity
+///
+/// Oldest first, so the admin queue is worked in order.
+pub async fn fetch_reports(
+    pool: &MySqlPool,
+    state: &str,
+    target: Option<&str>,
+) -> Result<Vec<Report>, sqlx::Error> {
+    sqlx::query_as!(
+        Report,
+        r#"
+        SELECT
+            r.id,
+            r.title,
+            r.description,
+            r.reporter_id,
+            r.state,
+            CASE
+                WHEN rt.trip_id IS NOT NULL THEN 'Trip'
+                WHEN ru.user_id IS NOT NULL THEN 'User'
+                WHEN rb.business_id IS NOT NULL THEN 'Business'
+                ELSE 'Product'
+            END as "target!: String",
+            COALESCE(rt.trip_id, ru.user_id, rb.business_id, rp.product_id) as "target_id!: i32",
+            r.resolution,
+            r.resolved_by,
+            r.created_at,
+            r.resolved_at
+        FROM reports r
+        LEFT JOIN report_trips rt ON rt.report_id = r.id
+        LEFT JOIN report_users ru ON ru.report_id = r.id
+        LEFT JOIN report_businesses rb ON rb.report_id = r.id
+        LEFT JOIN report_products rp ON rp.report_id = r.id
+        WHERE r.state = ?
+        HAVING (? IS NULL OR target = ?)
+        ORDER BY r.created_at ASC, r.id ASC
+        "#,
+        state,
+        target,
+        target
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Apply a moderation action to the reported entity
+///
+/// Returns whether the entity changed, so callers only update the stats once per deactivation.
+pub async fn apply_moderation_action(
+    conn: &mut MySqlConnection,
+    action: ModerationAction,
+    target_id: i32,
+) -> Result<bool, sqlx::Error> {
+    let result = match action {
+        ModerationAction::DeactivateUser => {
+            sqlx::query!(
+                "UPDATE users SET active = FALSE WHERE id = ? AND active = TRUE",
+                target_id
+            )
+            .execute(&mut *conn)
+            .await?
+        }
+        ModerationAction::UnverifyBusiness => {
+            sqlx::query!(
+                "UPDATE businesses SET verified = FALSE, verification_status = 'Pending' WHERE \
+                 id = ? AND verified = TRUE",
+                target_id
+            )
+            .execute(&mut *conn)
+            .await?
+        }
+        ModerationAction::DeactivateProduct => {
+            sqlx::query!(
+                "UPDATE products SET active = FALSE WHERE id = ? AND active = TRUE",
+                target_id
+            )
+            .execute(&mut *conn)
+            .await?
+        }
+    };
+
+    Ok(result.rows_affected() > 0)
+}
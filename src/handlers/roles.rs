@@ -0,0 +1,50 @@
+use sqlx::MySqlPool;
+
+use crate::middleware::rbac::Role;
+
+/// Resolve the roles of a user in a single query
+///
+/// Deactivated or deleted users get no roles at all.
+pub async fn fetch_roles(pool: &MySqlPool, user_id: i32) -> Result<Vec<Role>, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            EXISTS(SELECT 1 FROM admins WHERE user_id = u.id) as is_admin,
+            EXISTS(
+                SELECT 1 FROM businesses WHERE owner_id = u.id AND active = TRUE
+            ) as is_business_owner,
+            EXISTS(
+                SELECT 1
+                FROM business_employees be
+                JOIN businesses b ON be.business_id = b.id
+                WHERE be.user_id = u.id AND b.active = TRUE
+            ) as is_business_employee,
+            EXISTS(
+                SELECT 1 FROM drones WHERE user_id = u.id AND active = TRUE
+            ) as is_drone_operator
+        FROM users u
+        WHERE u.id = ? AND u.active = TRUE
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(Vec::new());
+    };
+
+    let roles = [
+        (Role::Admin, row.is_admin),
+        (Role::BusinessOwner, row.is_business_owner),
+        (Role::BusinessEmployee, row.is_business_employee),
+        (Role::DroneOperator, row.is_drone_operator),
+        (Role::Customer, 1),
+    ]
+    .into_iter()
+    .filter(|(_, held)| *held != 0)
+    .map(|(role, _)| role)
+    .collect();
+
+    Ok(roles)
+}
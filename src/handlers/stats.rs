@@ -1,4 +1,4 @@
-use sqlx::MySqlPool;
+use sqlx::{MySqlConnection, MySqlPool};
 
 /// Increment total_accounts and active_accounts by 1 when a new user is created
 pub async fn increment_user_stats(pool: &MySqlPool) -> Result<(), sqlx::Error> {
@@ -9,6 +9,14 @@ pub async fn increment_user_stats(pool: &MySqlPool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Count the new account in today's bucket
+    sqlx::query!(
+        "INSERT INTO daily_stats (day, new_accounts) VALUES (CURDATE(), 1) ON DUPLICATE KEY \
+         UPDATE new_accounts = new_accounts + 1"
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
 
@@ -21,5 +29,135 @@ pub async fn deactivate_user_stats(pool: &MySqlPool) -> Result<(), sqlx::Error>
     .execute(pool)
     .await?;
 
+    // Count the deactivation in today's bucket
+    sqlx::query!(
+        "INSERT INTO daily_stats (day, deactivated_accounts) VALUES (CURDATE(), 1) ON DUPLICATE \
+         KEY UPDATE deactivated_accounts = deactivated_accounts + 1"
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Add a trip to the daily bucket of the day it was requested and to the trip counts of the
+/// customer who placed the order
+///
+/// Runs in the transaction that registers the trip, and again after each transition, following
+/// [`remove_trip_stats`], so the counters stay exact without scanning the trips table. Buckets
+/// keep sums and counts instead of averages, so they can be merged into weeks and months.
+pub async fn add_trip_stats(conn: &mut MySqlConnection, trip_id: i32) -> Result<(), sqlx::Error> {
+    apply_trip_stats(conn, trip_id, 1).await
+}
+
+/// Remove a trip from the stats before a transition changes it, see [`add_trip_stats`]
+///
+/// The trip stays locked until the transaction ends, so what is removed here is exactly what
+/// the transition replaces.
+pub async fn remove_trip_stats(
+    conn: &mut MySqlConnection,
+    trip_id: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!("SELECT id FROM trips WHERE id = ? FOR UPDATE", trip_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+    apply_trip_stats(conn, trip_id, -1).await
+}
+
+// Add the values of a trip, multiplied by `sign`, to its daily bucket and its customer's counts
+async fn apply_trip_stats(
+    conn: &mut MySqlConnection,
+    trip_id: i32,
+    sign: i32,
+) -> Result<(), sqlx::Error> {
+    // Battery consumption only counts trips with both battery readings
+    sqlx::query!(
+        r#"
+        INSERT INTO daily_stats (
+            day,
+            trips,
+            finished_trips,
+            canceled_trips,
+            unfinished_trips,
+            total_delivery_time,
+            delivery_count,
+            total_packing_time,
+            packing_count,
+            battery_used,
+            battery_distance
+        )
+        SELECT
+            DATE(t.request_time),
+            s.sign,
+            s.sign * (t.state = 'Finished'),
+            s.sign * (t.state = 'Canceled'),
+            s.sign * (t.state = 'Unfinished'),
+            s.sign * COALESCE(t.delivery_time, 0),
+            s.sign * (t.delivery_time IS NOT NULL),
+            s.sign * COALESCE(t.packing_time, 0),
+            s.sign * (t.packing_time IS NOT NULL),
+            s.sign * CASE
+                WHEN t.battery_init IS NOT NULL AND t.battery_end IS NOT NULL
+                THEN t.battery_init - t.battery_end
+                ELSE 0
+            END,
+            s.sign * CASE
+                WHEN t.battery_init IS NOT NULL AND t.battery_end IS NOT NULL
+                THEN COALESCE(t.real_distance, t.distance)
+                ELSE 0
+            END
+        FROM trips t
+        CROSS JOIN (SELECT CAST(? AS SIGNED) as sign) s
+        WHERE t.id = ?
+        ON DUPLICATE KEY UPDATE
+            trips = trips + VALUES(trips),
+            finished_trips = finished_trips + VALUES(finished_trips),
+            canceled_trips = canceled_trips + VALUES(canceled_trips),
+            unfinished_trips = unfinished_trips + VALUES(unfinished_trips),
+            total_delivery_time = total_delivery_time + VALUES(total_delivery_time),
+            delivery_count = delivery_count + VALUES(delivery_count),
+            total_packing_time = total_packing_time + VALUES(total_packing_time),
+            packing_count = packing_count + VALUES(packing_count),
+            battery_used = battery_used + VALUES(battery_used),
+            battery_distance = battery_distance + VALUES(battery_distance)
+        "#,
+        sign,
+        trip_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO user_stats (user_id, trips, canceled_trips)
+        SELECT o.user_id, s.sign, s.sign * (t.state = 'Canceled')
+        FROM trips t
+        JOIN orders o ON t.order_id = o.id
+        CROSS JOIN (SELECT CAST(? AS SIGNED) as sign) s
+        WHERE t.id = ?
+        ON DUPLICATE KEY UPDATE
+            trips = trips + VALUES(trips),
+            canceled_trips = canceled_trips + VALUES(canceled_trips)
+        "#,
+        sign,
+        trip_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Stamp the user's last_login with the current time
+pub async fn update_last_login(pool: &MySqlPool, user_id: i32) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO user_stats (user_id, last_login) VALUES (?, NOW()) ON DUPLICATE KEY UPDATE \
+         last_login = NOW()",
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
@@ -0,0 +1,143 @@
+use chrono::{DateTime, Duration, Utc};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::{MySqlConnection, MySqlPool};
+
+/// Refresh tokens are only stored as their SHA-256 hash, so a database leak can't be replayed
+pub fn hash_refresh_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Why a refresh token was revoked, stored in `refresh_tokens.revoked_reason`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevokeReason {
+    /// Exchanged for a new token by `/auth/refresh`
+    Rotated,
+    /// Revoked by `/auth/logout`
+    Logout,
+    /// Revoked with the rest of its family after a rotated token was used again
+    Reused,
+}
+
+impl RevokeReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RevokeReason::Rotated => "Rotated",
+            RevokeReason::Logout => "Logout",
+            RevokeReason::Reused => "Reused",
+        }
+    }
+}
+
+/// Start a family of refresh tokens, one per login
+pub fn new_refresh_token_family() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Create a new refresh token for the user in `family_id` and return it in plain text
+///
+/// 32 random bytes, hex encoded. Expires after `REFRESH_TOKEN_EXPIRATION_DAYS`.
+pub async fn issue_refresh_token(
+    conn: &mut MySqlConnection,
+    user_id: i32,
+    family_id: &str,
+) -> Result<String, sqlx::Error> {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    let token: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    let expiration_days = crate::config::get_refresh_token_expiration_days();
+    let expires_at = (Utc::now() + Duration::days(expiration_days)).naive_utc();
+
+    sqlx::query!(
+        "INSERT INTO refresh_tokens (user_id, token_hash, family_id, expires_at) VALUES (?, ?, \
+         ?, ?)",
+        user_id,
+        hash_refresh_token(&token),
+        family_id,
+        expires_at
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(token)
+}
+
+/// Revoke every refresh token of the user that is still usable
+pub async fn revoke_user_refresh_tokens(
+    conn: &mut MySqlConnection,
+    user_id: i32,
+    reason: RevokeReason,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = UTC_TIMESTAMP(), revoked_reason = ? WHERE user_id \
+         = ? AND revoked_at IS NULL",
+        reason.as_str(),
+        user_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Revoke the refresh tokens of a family that are still usable
+pub async fn revoke_refresh_token_family(
+    conn: &mut MySqlConnection,
+    family_id: &str,
+    reason: RevokeReason,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = UTC_TIMESTAMP(), revoked_reason = ? WHERE \
+         family_id = ? AND revoked_at IS NULL",
+        reason.as_str(),
+        family_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Deny an access token until it expires
+pub async fn revoke_access_token(pool: &MySqlPool, jti: &str, exp: i64) -> Result<(), sqlx::Error> {
+    let expires_at = DateTime::from_timestamp(exp, 0)
+        .unwrap_or_else(Utc::now)
+        .naive_utc();
+
+    sqlx::query!(
+        "INSERT IGNORE INTO revoked_tokens (jti, expires_at) VALUES (?, ?)",
+        jti,
+        expires_at
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Whether an access token is on the denylist
+///
+/// Read from the database on every request, so a logout applies to every instance at once.
+pub async fn is_access_token_revoked(pool: &MySqlPool, jti: &str) -> Result<bool, sqlx::Error> {
+    let revoked = sqlx::query_scalar!(
+        "SELECT EXISTS(SELECT 1 FROM revoked_tokens WHERE jti = ?)",
+        jti
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(revoked != 0)
+}
+
+/// Drop the denylist entries of access tokens that have expired, they are rejected anyway
+pub async fn delete_expired_revoked_tokens(pool: &MySqlPool) -> Result<(), sqlx::Error> {
+    sqlx::query!("DELETE FROM revoked_tokens WHERE expires_at <= UTC_TIMESTAMP()")
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::models::{
+    location::Location,
+    trip::{Trip, TripState},
+};
+
+/// Flat row returned by the trip queries, before nesting the locations
+struct TripRow {
+    id: i32,
+    request_time: NaiveDateTime,
+    state: String,
+    weight: f64,
+    distance: f64,
+    est_time: f64,
+    arrival_time: Option<f64>,
+    total_time: Option<f64>,
+    real_distance: Option<f64>,
+    order_id: i32,
+    drone_id: i32,
+    from_location_id: i32,
+    from_name: Option<String>,
+    from_latitude: f64,
+    from_longitude: f64,
+    to_location_id: i32,
+    to_name: Option<String>,
+    to_latitude: f64,
+    to_longitude: f64,
+}
+
+impl TryFrom<TripRow> for Trip {
+    type Error = sqlx::Error;
+
+    fn try_from(row: TripRow) -> Result<Self, Self::Error> {
+        let state: TripState = row
+            .state
+            .parse()
+            .map_err(|_| sqlx::Error::Decode(format!("invalid trip state {}", row.state).into()))?;
+
+        Ok(Trip {
+            id: row.id,
+            request_time: row.request_time,
+            state,
+            weight: row.weight,
+            distance: row.distance,
+            est_time: row.est_time,
+            arrival_time: row.arrival_time,
+            total_time: row.total_time,
+            real_distance: row.real_distance,
+            order_id: row.order_id,
+            drone_id: row.drone_id,
+            from_location: Location {
+                id: row.from_location_id,
+                name: row.from_name,
+                latitude: row.from_latitude,
+                longitude: row.from_longitude,
+            },
+            to_location: Location {
+                id: row.to_location_id,
+                name: row.to_name,
+                latitude: row.to_latitude,
+                longitude: row.to_longitude,
+            },
+        })
+    }
+}
+
+/// Fetch a trip with both of its locations
+pub async fn fetch_trip(pool: &MySqlPool, trip_id: i32) -> Result<Option<Trip>, sqlx::Error> {
+    let row = sqlx::query_as!(
+        TripRow,
+        r#"
+        SELECT
+            t.id,
+            t.request_time,
+            t.state,
+            CAST(t.weight AS DOUBLE) as "weight!",
+            CAST(t.distance AS DOUBLE) as "distance!",
+            CAST(t.est_time AS DOUBLE) as "est_time!",
+            CAST(t.arrival_time AS DOUBLE) as arrival_time,
+            CAST(t.total_time AS DOUBLE) as total_time,
+            CAST(t.real_distance AS DOUBLE) as real_distance,
+            t.order_id,
+            t.drone_id,
+            lf.id as from_location_id,
+            lf.name as from_name,
+            CAST(lf.latitude AS DOUBLE) as "from_latitude!",
+            CAST(lf.longitude AS DOUBLE) as "from_longitude!",
+            lt.id as to_location_id,
+            lt.name as to_name,
+            CAST(lt.latitude AS DOUBLE) as "to_latitude!",
+            CAST(lt.longitude AS DOUBLE) as "to_longitude!"
+        FROM trips t
+        JOIN locations lf ON t.from_location_id = lf.id
+        JOIN locations lt ON t.to_location_id = lt.id
+        WHERE t.id = ?
+        "#,
+        trip_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    row.map(Trip::try_from).transpose()
+}
+
+/// Fetch every trip performed by a drone, newest first
+pub async fn fetch_trips_by_drone(
+    pool: &MySqlPool,
+    drone_id: i32,
+) -> Result<Vec<Trip>, sqlx::Error> {
+    let rows = sqlx::query_as!(
+        TripRow,
+        r#"
+        SELECT
+            t.id,
+            t.request_time,
+            t.state,
+            CAST(t.weight AS DOUBLE) as "weight!",
+            CAST(t.distance AS DOUBLE) as "distance!",
+            CAST(t.est_time AS DOUBLE) as "est_time!",
+            CAST(t.arrival_time AS DOUBLE) as arrival_time,
+            CAST(t.total_time AS DOUBLE) as total_time,
+            CAST(t.real_distance AS DOUBLE) as real_distance,
+            t.order_id,
+            t.drone_id,
+            lf.id as from_location_id,
+            lf.name as from_name,
+            CAST(lf.latitude AS DOUBLE) as "from_latitude!",
+            CAST(lf.longitude AS DOUBLE) as "from_longitude!",
+            lt.id as to_location_id,
+            lt.name as to_name,
+            CAST(lt.latitude AS DOUBLE) as "to_latitude!",
+            CAST(lt.longitude AS DOUBLE) as "to_longitude!"
+        FROM trips t
+        JOIN locations lf ON t.from_location_id = lf.id
+        JOIN locations lt ON t.to_location_id = lt.id
+        WHERE t.drone_id = ?
+        ORDER BY t.request_time DESC
+        "#,
+        drone_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter().map(Trip::try_from).collect()
+}
+
+/// Fetch the most recent trip of an order
+pub async fn fetch_latest_trip_by_order(
+    pool: &MySqlPool,
+    order_id: i32,
+) -> Result<Option<Trip>, sqlx::Error> {
+    let row = sqlx::query_as!(
+        TripRow,
+        r#"
+        SELECT
+            t.id,
+            t.request_time,
+            t.state,
+            CAST(t.weight AS DOUBLE) as "weight!",
+            CAST(t.distance AS DOUBLE) as "distance!",
+            CAST(t.est_time AS DOUBLE) as "est_time!",
+            CAST(t.arrival_time AS DOUBLE) as arrival_time,
+            CAST(t.total_time AS DOUBLE) as total_time,
+            CAST(t.real_distance AS DOUBLE) as real_distance,
+            t.order_id,
+            t.drone_id,
+            lf.id as from_location_id,
+            lf.name as from_name,
+            CAST(lf.latitude AS DOUBLE) as "from_latitude!",
+            CAST(lf.longitude AS DOUBLE) as "from_longitude!",
+            lt.id as to_location_id,
+            lt.name as to_name,
+            CAST(lt.latitude AS DOUBLE) as "to_latitude!",
+            CAST(lt.longitude AS DOUBLE) as "to_longitude!"
+        FROM trips t
+        JOIN locations lf ON t.from_location_id = lf.id
+        JOIN locations lt ON t.to_location_id = lt.id
+        WHERE t.order_id = ?
+        ORDER BY t.request_time DESC, t.id DESC
+        LIMIT 1
+        "#,
+        order_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    row.map(Trip::try_from).transpose()
+}
+
+/// Whether a user can see a trip: the drone owner, the customer who placed the order,
+/// or the owner of the business selling the order's products
+pub async fn can_view_trip(
+    pool: &MySqlPool,
+    trip_id: i32,
+    user_id: i32,
+) -> Result<bool, sqlx::Error> {
+    let access = sqlx::query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1
+            FROM trips t
+            JOIN drones d ON t.drone_id = d.id
+            JOIN orders o ON t.order_id = o.id
+            WHERE t.id = ?
+              AND (
+                d.user_id = ?
+                OR o.user_id = ?
+                OR EXISTS(
+                    SELECT 1
+                    FROM order_details od
+                    JOIN products p ON od.product_id = p.id
+                    JOIN businesses b ON p.business_id = b.id
+                    WHERE od.order_id = o.id AND b.owner_id = ?
+                )
+              )
+        ) as allowed
+        "#,
+        trip_id,
+        user_id,
+        user_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(access.allowed != 0)
+}
+
+/// Whether the order has a trip that is 'Requested' or 'Delivered'
+pub async fn has_trip_in_progress(
+    conn: &mut MySqlConnection,
+    order_id: i32,
+) -> Result<bool, sqlx::Error> {
+    let active_trip = sqlx::query!(
+        "SELECT EXISTS(SELECT 1 FROM trips WHERE order_id = ? AND state IN ('Requested', \
+         'Delivered')) as active",
+        order_id
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(active_trip.active != 0)
+}
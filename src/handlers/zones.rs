@@ -0,0 +1,54 @@
+use sqlx::MySqlPool;
+
+use crate::geo::{Coordinates, distance_to_segment};
+
+/// A prohibited zone that blocks a route
+pub struct BlockingZone {
+    pub id: i32,
+    pub description: String,
+}
+
+/// Find the first prohibited zone crossed by the straight-line route between `from` and `to`
+///
+/// Only zones whose active window overlaps the flight (from now until `est_time` minutes
+/// from now) are considered.
+pub async fn find_blocking_zone(
+    pool: &MySqlPool,
+    from: Coordinates,
+    to: Coordinates,
+    est_time: f64,
+) -> Result<Option<BlockingZone>, sqlx::Error> {
+    let flight_seconds = (est_time * 60.0).ceil() as i64;
+
+    let zones = sqlx::query!(
+        r#"
+        SELECT
+            z.id,
+            z.description,
+            CAST(z.radius AS DOUBLE) as "radius!",
+            CAST(l.latitude AS DOUBLE) as "latitude!",
+            CAST(l.longitude AS DOUBLE) as "longitude!"
+        FROM prohibited_zones z
+        JOIN locations l ON z.location_id = l.id
+        WHERE z.since <= DATE_ADD(NOW(), INTERVAL ? SECOND)
+          AND (z.`to` IS NULL OR z.`to` > NOW())
+        ORDER BY z.id ASC
+        "#,
+        flight_seconds
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let blocking_zone = zones
+        .into_iter()
+        .find(|zone| {
+            let center = Coordinates::new(zone.latitude, zone.longitude);
+            distance_to_segment(center, from, to) <= zone.radius
+        })
+        .map(|zone| BlockingZone {
+            id: zone.id,
+            description: zone.description,
+        });
+
+    Ok(blocking_zone)
+}
@@ -0,0 +1,235 @@
+pub mod config;
+pub mod error;
+pub mod geo;
+pub mod handlers;
+pub mod middleware;
+pub mod models;
+pub mod repositories;
+pub mod routes;
+pub mod services;
+
+use axum::Router;
+use routes::{
+    business::{
+        add_employee::add_employee, analytics::get_business_analytics, delete::delete_business,
+        list::list_businesses, list_employees::list_employees, pending::list_pending_businesses,
+        register::register_business, reject::reject_business, remove_employee::remove_employee,
+        set_location::set_business_location, update::update_business, verify::verify_business,
+    },
+    drones::{
+        delete::delete_drone, list_drones::list_drones, metrics::get_drone_metrics,
+        register::register_drone,
+    },
+    orders::{
+        approve::approve_order, cancel::cancel_order, get::get_order, list::list_orders,
+        list_by_business::list_orders_by_business, register::register_order, reject::reject_order,
+    },
+    product::{
+        delete::delete_product, list_by_business::list_products_by_business,
+        register::register_product, update::update_product,
+    },
+    reports::{
+        dismiss::dismiss_report, list::list_reports, register::register_report,
+        resolve::resolve_report,
+    },
+    stats::stats_::get_stats,
+    telemetry::{list::list_telemetry, record::record_telemetry},
+    trips::{
+        cancel::cancel_trip, deliver::deliver_trip, events::stream_trip_events, fail::fail_trip,
+        finish::finish_trip, get::get_trip, get_by_order::get_trip_by_order,
+        list_by_drone::list_trips_by_drone, register::register_trip,
+    },
+    users::{
+        delete::delete_user,
+        jwks::get_jwks,
+        login::{AppState, login},
+        logout::logout,
+        refresh::refresh,
+        register::register_handler,
+        stats::get_user_stats,
+        update::update_user,
+    },
+    zones::{delete::delete_zone, list::list_zones, register::register_zone, update::update_zone},
+};
+use utoipa::openapi::{
+    security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    tag::TagBuilder,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::routes::{
+    business::{
+        add_employee::__path_add_employee, analytics::__path_get_business_analytics,
+        delete::__path_delete_business, list::__path_list_businesses,
+        list_employees::__path_list_employees, pending::__path_list_pending_businesses,
+        register::__path_register_business, reject::__path_reject_business,
+        remove_employee::__path_remove_employee, set_location::__path_set_business_location,
+        update::__path_update_business, verify::__path_verify_business,
+    },
+    drones::{
+        delete::__path_delete_drone, list_drones::__path_list_drones,
+        metrics::__path_get_drone_metrics, register::__path_register_drone,
+    },
+    orders::{
+        approve::__path_approve_order, cancel::__path_cancel_order, get::__path_get_order,
+        list::__path_list_orders, list_by_business::__path_list_orders_by_business,
+        register::__path_register_order, reject::__path_reject_order,
+    },
+    product::{
+        delete::__path_delete_product, list_by_business::__path_list_products_by_business,
+        register::__path_register_product, update::__path_update_product,
+    },
+    reports::{
+        dismiss::__path_dismiss_report, list::__path_list_reports,
+        register::__path_register_report, resolve::__path_resolve_report,
+    },
+    stats::stats_::__path_get_stats,
+    telemetry::{list::__path_list_telemetry, record::__path_record_telemetry},
+    trips::{
+        cancel::__path_cancel_trip, deliver::__path_deliver_trip,
+        events::__path_stream_trip_events, fail::__path_fail_trip, finish::__path_finish_trip,
+        get::__path_get_trip, get_by_order::__path_get_trip_by_order,
+        list_by_drone::__path_list_trips_by_drone, register::__path_register_trip,
+    },
+    users::{
+        delete::__path_delete_user, jwks::__path_get_jwks, login::__path_login,
+        logout::__path_logout, refresh::__path_refresh, register::__path_register_handler,
+        stats::__path_get_user_stats, update::__path_update_user,
+    },
+    zones::{
+        delete::__path_delete_zone, list::__path_list_zones, register::__path_register_zone,
+        update::__path_update_zone,
+    },
+};
+
+/// Build the application: every route, the OpenAPI document and the Swagger UI
+///
+/// Shared by the server and the integration tests, so both run the exact same router.
+pub fn app(state: AppState) -> Router {
+    let (api_router, mut api) = OpenApiRouter::new()
+        .routes(routes!(login))
+        .routes(routes!(refresh))
+        .routes(routes!(logout))
+        .routes(routes!(get_jwks))
+        .routes(routes!(register_handler))
+        .routes(routes!(delete_user))
+        .routes(routes!(update_user))
+        .routes(routes!(get_user_stats))
+        .routes(routes!(get_stats))
+        .routes(routes!(register_drone))
+        .routes(routes!(list_drones))
+        .routes(routes!(delete_drone))
+        .routes(routes!(get_drone_metrics))
+        .routes(routes!(register_business))
+        .routes(routes!(list_businesses))
+        .routes(routes!(update_business))
+        .routes(routes!(delete_business))
+        .routes(routes!(set_business_location))
+        .routes(routes!(get_business_analytics))
+        .routes(routes!(list_pending_businesses))
+        .routes(routes!(verify_business))
+        .routes(routes!(reject_business))
+        .routes(routes!(add_employee))
+        .routes(routes!(list_employees))
+        .routes(routes!(remove_employee))
+        .routes(routes!(register_product))
+        .routes(routes!(update_product))
+        .routes(routes!(delete_product))
+        .routes(routes!(list_products_by_business))
+        .routes(routes!(register_order))
+        .routes(routes!(approve_order))
+        .routes(routes!(reject_order))
+        .routes(routes!(cancel_order))
+        .routes(routes!(list_orders))
+        .routes(routes!(get_order))
+        .routes(routes!(list_orders_by_business))
+        .routes(routes!(register_trip))
+        .routes(routes!(deliver_trip))
+        .routes(routes!(finish_trip))
+        .routes(routes!(cancel_trip))
+        .routes(routes!(fail_trip))
+        .routes(routes!(get_trip))
+        .routes(routes!(list_trips_by_drone))
+        .routes(routes!(get_trip_by_order))
+        .routes(routes!(stream_trip_events))
+        .routes(routes!(record_telemetry))
+        .routes(routes!(list_telemetry))
+        .routes(routes!(register_zone))
+        .routes(routes!(list_zones))
+        .routes(routes!(update_zone))
+        .routes(routes!(delete_zone))
+        .routes(routes!(register_report))
+        .routes(routes!(list_reports))
+        .routes(routes!(resolve_report))
+        .routes(routes!(dismiss_report))
+        .split_for_parts();
+
+    api.components
+        .get_or_insert_with(Default::default)
+        .add_security_scheme(
+            "jwt",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .description(Some(
+                        "Scopes name the roles a route accepts, holding any of them is enough: \
+                         admin, business_owner, business_employee, drone_operator or customer",
+                    ))
+                    .build(),
+            ),
+        );
+
+    api.tags = Some(vec![
+        TagBuilder::new()
+            .name("Authentication")
+            .description(Some("Authentication endpoints"))
+            .build(),
+        TagBuilder::new()
+            .name("Users")
+            .description(Some("User management endpoints"))
+            .build(),
+        TagBuilder::new()
+            .name("Business")
+            .description(Some("Business management endpoints"))
+            .build(),
+        TagBuilder::new()
+            .name("Products")
+            .description(Some("Product management endpoints"))
+            .build(),
+        TagBuilder::new()
+            .name("Drones")
+            .description(Some("Drone management endpoints"))
+            .build(),
+        TagBuilder::new()
+            .name("Orders")
+            .description(Some("Order management endpoints"))
+            .build(),
+        TagBuilder::new()
+            .name("Trips")
+            .description(Some("Trip management endpoints"))
+            .build(),
+        TagBuilder::new()
+            .name("Telemetry")
+            .description(Some("Drone telemetry endpoints"))
+            .build(),
+        TagBuilder::new()
+            .name("Zones")
+            .description(Some("Prohibited zone management endpoints"))
+            .build(),
+        TagBuilder::new()
+            .name("Reports")
+            .description(Some("Reporting and moderation endpoints"))
+            .build(),
+        TagBuilder::new()
+            .name("Stats")
+            .description(Some("Statistics endpoints"))
+            .build(),
+    ]);
+
+    Router::new()
+        .merge(api_router)
+        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", api))
+        .with_state(state)
+}
@@ -1,7 +1,7 @@
 use axum::{
     RequestPartsExt,
     extract::FromRequestParts,
-    http::{StatusCode, request::Parts},
+    http::request::Parts,
     response::{IntoResponse, Response},
 };
 use axum_extra::{
@@ -9,15 +9,23 @@ use axum_extra::{
     headers::{Authorization, authorization::Bearer},
 };
 use chrono::{Duration, Utc};
-use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use jsonwebtoken::{Header, Validation, decode, decode_header, encode};
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    error::ApiError,
+    handlers::tokens::is_access_token_revoked,
+    middleware::{keys::JWT_KEYS, rbac::Role},
+    routes::users::login::AppState,
+};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String,      // Subject (user id)
     pub username: String, // Username
     pub exp: i64,         // Expiration time
     pub iat: i64,         // Issued at
+    pub jti: String,      // Token id, used to revoke the token
 }
 
 impl Claims {
@@ -31,29 +39,34 @@ impl Claims {
             username,
             exp: exp.timestamp(),
             iat: now.timestamp(),
+            jti: uuid::Uuid::new_v4().to_string(),
         }
     }
 }
 
-// Create a JWT token
+// Create a JWT token, signed with the current key (see middleware::keys)
 pub fn create_token(user_id: i32, username: String) -> Result<String, jsonwebtoken::errors::Error> {
     let claims = Claims::new(user_id, username);
-    let secret = crate::config::get_jwt_secret();
-    encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(secret.as_bytes()),
-    )
+    let keys = &*JWT_KEYS;
+
+    let mut header = Header::new(keys.algorithm);
+    header.kid = keys.signing_kid.clone();
+
+    encode(&header, &claims, &keys.encoding)
 }
 
-// Validate and decode a JWT token
+// Validate and decode a JWT token, picking the key by the `kid` of its header
+//
+// Only checks the signature and expiration, revoked tokens are rejected by the extractor.
 pub fn validate_token(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
-    let secret = crate::config::get_jwt_secret();
-    let token_data = decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(secret.as_bytes()),
-        &Validation::default(),
-    )?;
+    let keys = &*JWT_KEYS;
+    let header = decode_header(token)?;
+    let key = keys
+        .decoding_key(header.kid.as_deref())
+        .ok_or(jsonwebtoken::errors::ErrorKind::InvalidToken)?;
+
+    let token_data = decode::<Claims>(token, key, &Validation::new(keys.algorithm))?;
+
     Ok(token_data.claims)
 }
 
@@ -62,27 +75,24 @@ pub enum AuthError {
     InvalidToken,
     MissingToken,
     TokenExpired,
+    MissingRole(&'static [Role]),
+    Internal,
 }
 
 impl IntoResponse for AuthError {
     fn into_response(self) -> Response {
-        let (status, message) = match self {
-            AuthError::InvalidToken => (StatusCode::UNAUTHORIZED, "Invalid token"),
-            AuthError::MissingToken => (StatusCode::UNAUTHORIZED, "Missing token"),
-            AuthError::TokenExpired => (StatusCode::UNAUTHORIZED, "Token expired"),
-        };
-        (status, message).into_response()
+        ApiError::from(self).into_response()
     }
 }
 
 // Extractor for authenticated requests
-impl<S> FromRequestParts<S> for Claims
-where
-    S: Send + Sync,
-{
+impl FromRequestParts<AppState> for Claims {
     type Rejection = AuthError;
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
         // Extract the token from the authorization header
         let TypedHeader(Authorization(bearer)) = parts
             .extract::<TypedHeader<Authorization<Bearer>>>()
@@ -90,10 +100,20 @@ where
             .map_err(|_| AuthError::MissingToken)?;
 
         // Validate the token
-        validate_token(bearer.token()).map_err(|err| match err.kind() {
+        let claims = validate_token(bearer.token()).map_err(|err| match err.kind() {
             jsonwebtoken::errors::ErrorKind::ExpiredSignature => AuthError::TokenExpired,
             _ => AuthError::InvalidToken,
-        })
+        })?;
+
+        // Reject tokens revoked by a logout, on any instance
+        let revoked = is_access_token_revoked(&state.db, &claims.jti)
+            .await
+            .map_err(|_| AuthError::Internal)?;
+        if revoked {
+            return Err(AuthError::InvalidToken);
+        }
+
+        Ok(claims)
     }
 }
 
@@ -0,0 +1,137 @@
+use std::{collections::HashMap, fs, sync::LazyLock};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use ed25519_dalek::VerifyingKey;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use rsa::{RsaPublicKey, traits::PublicKeyParts};
+
+use crate::models::jwk::{Jwk, Jwks};
+
+/// Keys used to sign and verify tokens, loaded once from the environment
+///
+/// - HS256 uses `JWT_SECRET` and publishes no keys
+/// - RS256 and EdDSA sign with the private key `JWT_SIGNING_KEY_ID` and accept every key in
+///   `JWT_PUBLIC_KEYS`, so a new key can be introduced before the old one is retired
+pub struct JwtKeys {
+    pub algorithm: Algorithm,
+    /// Key ID written in the header of new tokens, None for HS256
+    pub signing_kid: Option<String>,
+    pub encoding: EncodingKey,
+    decoding: HashMap<String, DecodingKey>,
+    /// Used for HS256 tokens, which carry no key ID
+    shared: Option<DecodingKey>,
+    pub jwks: Jwks,
+}
+
+pub static JWT_KEYS: LazyLock<JwtKeys> = LazyLock::new(JwtKeys::from_env);
+
+impl JwtKeys {
+    fn from_env() -> Self {
+        let algorithm = match crate::config::get_jwt_algorithm().as_str() {
+            "HS256" => Algorithm::HS256,
+            "RS256" => Algorithm::RS256,
+            "EdDSA" => Algorithm::EdDSA,
+            other => panic!("JWT_ALGORITHM must be HS256, RS256 or EdDSA, got {}", other),
+        };
+
+        if algorithm == Algorithm::HS256 {
+            let secret = crate::config::get_jwt_secret();
+            return Self {
+                algorithm,
+                signing_kid: None,
+                encoding: EncodingKey::from_secret(secret.as_bytes()),
+                decoding: HashMap::new(),
+                shared: Some(DecodingKey::from_secret(secret.as_bytes())),
+                jwks: Jwks { keys: Vec::new() },
+            };
+        }
+
+        // Load the private key used to sign new tokens
+        let (signing_kid, private_key_path) = crate::config::get_jwt_signing_key();
+        let private_pem = fs::read(&private_key_path)
+            .unwrap_or_else(|err| panic!("Failed to read {}: {}", private_key_path, err));
+        let encoding = match algorithm {
+            Algorithm::RS256 => EncodingKey::from_rsa_pem(&private_pem),
+            _ => EncodingKey::from_ed_pem(&private_pem),
+        }
+        .unwrap_or_else(|err| panic!("Invalid private key {}: {}", private_key_path, err));
+
+        // Load every public key accepted for verification
+        let mut decoding = HashMap::new();
+        let mut keys = Vec::new();
+        for (kid, path) in crate::config::get_jwt_public_keys() {
+            let pem = fs::read_to_string(&path)
+                .unwrap_or_else(|err| panic!("Failed to read {}: {}", path, err));
+
+            let (decoding_key, jwk) = match algorithm {
+                Algorithm::RS256 => rsa_public_key(&kid, &pem),
+                _ => ed_public_key(&kid, &pem),
+            }
+            .unwrap_or_else(|err| panic!("Invalid public key {}: {}", path, err));
+
+            decoding.insert(kid, decoding_key);
+            keys.push(jwk);
+        }
+
+        assert!(
+            decoding.contains_key(&signing_kid),
+            "JWT_PUBLIC_KEYS must include the signing key {}",
+            signing_kid
+        );
+
+        Self {
+            algorithm,
+            signing_kid: Some(signing_kid),
+            encoding,
+            decoding,
+            shared: None,
+            jwks: Jwks { keys },
+        }
+    }
+
+    /// Key to verify a token with, picked by the `kid` of its header
+    pub fn decoding_key(&self, kid: Option<&str>) -> Option<&DecodingKey> {
+        match kid {
+            Some(kid) => self.decoding.get(kid),
+            None => self.shared.as_ref(),
+        }
+    }
+}
+
+fn rsa_public_key(kid: &str, pem: &str) -> Result<(DecodingKey, Jwk), String> {
+    let key = <RsaPublicKey as rsa::pkcs8::DecodePublicKey>::from_public_key_pem(pem)
+        .map_err(|err| err.to_string())?;
+    let decoding = DecodingKey::from_rsa_pem(pem.as_bytes()).map_err(|err| err.to_string())?;
+
+    let jwk = Jwk {
+        kty: "RSA".to_string(),
+        kid: kid.to_string(),
+        alg: "RS256".to_string(),
+        key_use: "sig".to_string(),
+        n: Some(URL_SAFE_NO_PAD.encode(key.n().to_bytes_be())),
+        e: Some(URL_SAFE_NO_PAD.encode(key.e().to_bytes_be())),
+        crv: None,
+        x: None,
+    };
+
+    Ok((decoding, jwk))
+}
+
+fn ed_public_key(kid: &str, pem: &str) -> Result<(DecodingKey, Jwk), String> {
+    let key = <VerifyingKey as ed25519_dalek::pkcs8::DecodePublicKey>::from_public_key_pem(pem)
+        .map_err(|err| err.to_string())?;
+    let decoding = DecodingKey::from_ed_pem(pem.as_bytes()).map_err(|err| err.to_string())?;
+
+    let jwk = Jwk {
+        kty: "OKP".to_string(),
+        kid: kid.to_string(),
+        alg: "EdDSA".to_string(),
+        key_use: "sig".to_string(),
+        n: None,
+        e: None,
+        crv: Some("Ed25519".to_string()),
+        x: Some(URL_SAFE_NO_PAD.encode(key.as_bytes())),
+    };
+
+    Ok((decoding, jwk))
+}
@@ -1 +1,4 @@
 pub mod auth;
+pub mod keys;
+pub mod rbac;
+pub mod validation;
@@ -0,0 +1,152 @@
+use std::marker::PhantomData;
+
+use axum::{extract::FromRequestParts, http::request::Parts};
+
+use crate::{
+    handlers::roles::fetch_roles,
+    middleware::auth::{AuthError, Claims},
+    routes::users::login::AppState,
+};
+
+/// Roles a user can hold, resolved from the database on every request
+///
+/// Roles are not stored in the token, so granting or revoking one takes effect immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Listed in the admins table
+    Admin,
+    /// Owns at least one active business
+    BusinessOwner,
+    /// Works for at least one active business
+    BusinessEmployee,
+    /// Owns at least one active drone
+    DroneOperator,
+    /// Any active user
+    Customer,
+}
+
+impl Role {
+    /// Name of the role, also used as the scope of the `jwt` security requirement
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::BusinessOwner => "business_owner",
+            Role::BusinessEmployee => "business_employee",
+            Role::DroneOperator => "drone_operator",
+            Role::Customer => "customer",
+        }
+    }
+}
+
+/// Authenticated user with its roles
+#[derive(Debug)]
+pub struct AuthUser {
+    pub id: i32,
+    pub roles: Vec<Role>,
+}
+
+impl AuthUser {
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles.contains(&role)
+    }
+
+    /// Whether the user is `user_id` or an admin
+    pub fn is_self_or_admin(&self, user_id: i32) -> bool {
+        self.id == user_id || self.has_role(Role::Admin)
+    }
+}
+
+// Extractor for authenticated requests that need the user's roles
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let claims = Claims::from_request_parts(parts, state).await?;
+        let id = claims.sub.parse().map_err(|_| AuthError::InvalidToken)?;
+
+        // Resolve the roles once for the whole request
+        let roles = fetch_roles(&state.db, id)
+            .await
+            .map_err(|_| AuthError::Internal)?;
+
+        Ok(Self { id, roles })
+    }
+}
+
+/// Roles accepted by a route, see [`Authorized`]
+pub trait RequiredRole {
+    /// Holding any of these roles is enough
+    const ROLES: &'static [Role];
+}
+
+/// Marker for routes restricted to admins
+pub struct Admin;
+
+impl RequiredRole for Admin {
+    const ROLES: &'static [Role] = &[Role::Admin];
+}
+
+/// Marker for routes restricted to business owners
+pub struct BusinessOwner;
+
+impl RequiredRole for BusinessOwner {
+    const ROLES: &'static [Role] = &[Role::BusinessOwner];
+}
+
+/// Marker for routes restricted to business employees
+pub struct BusinessEmployee;
+
+impl RequiredRole for BusinessEmployee {
+    const ROLES: &'static [Role] = &[Role::BusinessEmployee];
+}
+
+/// Marker for routes open to business owners and employees
+pub struct BusinessStaff;
+
+impl RequiredRole for BusinessStaff {
+    const ROLES: &'static [Role] = &[Role::BusinessOwner, Role::BusinessEmployee];
+}
+
+/// Marker for routes restricted to drone operators
+pub struct DroneOperator;
+
+impl RequiredRole for DroneOperator {
+    const ROLES: &'static [Role] = &[Role::DroneOperator];
+}
+
+/// Marker for routes open to any active user
+pub struct Customer;
+
+impl RequiredRole for Customer {
+    const ROLES: &'static [Role] = &[Role::Customer];
+}
+
+/// Extractor that rejects users without any of the roles of `R` with 403
+///
+/// Routes declare the role in their signature, e.g. `Authorized(user, _): Authorized<Admin>`,
+/// and in their OpenAPI security requirement, e.g. `("jwt" = ["admin"])`. Checking that the
+/// user owns the resource is still up to the route.
+pub struct Authorized<R>(pub AuthUser, pub PhantomData<R>);
+
+impl<R> FromRequestParts<AppState> for Authorized<R>
+where
+    R: RequiredRole + Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state).await?;
+
+        if !R::ROLES.iter().any(|role| user.has_role(*role)) {
+            return Err(AuthError::MissingRole(R::ROLES));
+        }
+
+        Ok(Self(user, PhantomData))
+    }
+}
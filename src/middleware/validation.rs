@@ -0,0 +1,70 @@
+use axum::{
+    Json,
+    extract::{FromRequest, FromRequestParts, Query, Request},
+    http::request::Parts,
+};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationError};
+
+use crate::error::ApiError;
+
+/// JSON body extractor that runs the `validator` rules of its model
+///
+/// Malformed bodies are rejected with "invalid_body", and bodies that break a rule with
+/// "validation_failed" and the list of invalid fields.
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        // Parse the body
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(|rejection| ApiError::bad_request("invalid_body", rejection.body_text()))?;
+
+        // Check the rules declared on the model
+        value.validate()?;
+
+        Ok(ValidatedJson(value))
+    }
+}
+
+/// Query string extractor that runs the `validator` rules of its model
+///
+/// Malformed query strings are rejected with "invalid_query", and the ones that break a rule
+/// with "validation_failed" and the list of invalid fields.
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        // Parse the query string
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| ApiError::bad_request("invalid_query", rejection.body_text()))?;
+
+        // Check the rules declared on the model
+        value.validate()?;
+
+        Ok(ValidatedQuery(value))
+    }
+}
+
+// Reject strings made only of whitespace, which pass a `length(min = 1)` rule
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("blank").with_message("must not be blank".into()));
+    }
+
+    Ok(())
+}
@@ -1,8 +1,15 @@
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+use validator::Validate;
 
-#[derive(Deserialize, utoipa::ToSchema)]
+use crate::middleware::validation::not_blank;
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct RegisterBusinessRequest {
+    #[validate(length(min = 1, max = 100), custom(function = "not_blank"))]
     pub name: String,
+    #[validate(length(max = 1000))]
     pub description: String,
 }
 
@@ -13,13 +20,63 @@ pub struct RegisterBusinessResponse {
     pub description: String,
     pub owner_id: i32,
     pub verified: bool,
+    pub verification_status: VerificationStatus,
     pub active: bool,
     pub message: String,
 }
 
-#[derive(Deserialize, utoipa::ToSchema)]
+/// Verification states of a business, only 'Verified' businesses can sell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum VerificationStatus {
+    /// Waiting for an admin to review the business
+    Pending,
+    Verified,
+    Rejected,
+}
+
+impl VerificationStatus {
+    /// Returns the value stored in the `businesses.verification_status` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationStatus::Pending => "Pending",
+            VerificationStatus::Verified => "Verified",
+            VerificationStatus::Rejected => "Rejected",
+        }
+    }
+}
+
+impl std::str::FromStr for VerificationStatus {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "Pending" => Ok(VerificationStatus::Pending),
+            "Verified" => Ok(VerificationStatus::Verified),
+            "Rejected" => Ok(VerificationStatus::Rejected),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct RejectBusinessRequest {
+    /// Why the business was rejected, shown to the owner
+    #[validate(length(min = 1, max = 1000), custom(function = "not_blank"))]
+    pub reason: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct VerificationResponse {
+    pub business_id: i32,
+    pub verification_status: VerificationStatus,
+    pub message: String,
+}
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct UpdateBusinessRequest {
+    #[validate(length(min = 1, max = 100), custom(function = "not_blank"))]
     pub name: Option<String>,
+    #[validate(length(max = 1000))]
     pub description: Option<String>,
 }
 
@@ -47,6 +104,88 @@ pub struct Business {
     pub owner_id: i32,
     /// Whether the business is verified
     pub verified: i8,
+    /// Review state of the business: Pending, Verified or Rejected
+    pub verification_status: String,
+    /// Why an admin rejected the business, NULL unless it was rejected
+    pub rejection_reason: Option<String>,
     /// Whether the business is active
     pub active: i8,
 }
+
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AnalyticsQuery {
+    /// First day of the range, defaults to the first order
+    pub from: Option<NaiveDate>,
+    /// Last day of the range (inclusive), defaults to today
+    pub to: Option<NaiveDate>,
+    /// How many best-selling products to return (1 to 50), defaults to 5
+    #[validate(range(min = 1, max = 50))]
+    #[param(minimum = 1, maximum = 50)]
+    pub top: Option<u32>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct TopProduct {
+    /// Product ID
+    pub product_id: i32,
+    /// Current name of the product
+    pub name: String,
+    /// Units sold in the range
+    pub units_sold: i64,
+    /// Revenue of the product in the range
+    pub revenue: f64,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct BusinessAnalytics {
+    /// Business ID
+    pub business_id: i32,
+    /// First day of the range, NULL if unbounded
+    pub from: Option<NaiveDate>,
+    /// Last day of the range, NULL if unbounded
+    pub to: Option<NaiveDate>,
+    /// Revenue of the business' products in finished orders
+    pub revenue: f64,
+    /// Finished orders including at least one product of the business
+    pub order_count: i64,
+    /// revenue / order_count, 0 when there are no orders
+    pub average_order_value: f64,
+    /// Best-selling products by units sold
+    pub top_products: Vec<TopProduct>,
+}
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct AddEmployeeRequest {
+    /// Username of the user to add as an employee
+    #[validate(length(min = 1, max = 50))]
+    pub username: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct AddEmployeeResponse {
+    pub business_id: i32,
+    pub user_id: i32,
+    pub message: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct RemoveEmployeeResponse {
+    pub business_id: i32,
+    pub user_id: i32,
+    pub message: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct Employee {
+    /// User ID of the employee
+    pub user_id: i32,
+    /// Username of the employee
+    pub username: String,
+    /// First name of the employee
+    pub name: String,
+    /// Last name of the employee
+    pub lastname: String,
+    /// When the employee was added to the business
+    pub added_at: NaiveDateTime,
+}
@@ -1,11 +1,20 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
-#[derive(Deserialize, utoipa::ToSchema)]
+use crate::middleware::validation::not_blank;
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct RegisterDroneRequest {
     /// Name of the drone
+    #[validate(length(min = 1, max = 100), custom(function = "not_blank"))]
     pub name: String,
     /// Unique drone number
+    #[validate(range(min = 1))]
     pub number: i32,
+    /// Cruise speed in km/h, defaults to 40 km/h when omitted
+    #[validate(range(exclusive_min = 0.0, max = 300.0))]
+    pub cruise_speed: Option<f64>,
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
@@ -16,6 +25,8 @@ pub struct RegisterDroneResponse {
     pub name: String,
     /// Drone number
     pub number: i32,
+    /// Cruise speed in km/h
+    pub cruise_speed: f64,
     /// Success message
     pub message: String,
 }
@@ -34,8 +45,26 @@ pub struct Drone {
     pub name: String,
     /// Unique drone number
     pub number: i32,
+    /// Cruise speed in km/h
+    pub cruise_speed: f64,
     /// Owner user ID
     pub user_id: i32,
     /// Whether the drone is active
     pub active: i8,
 }
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct DroneMetrics {
+    /// Drone ID
+    pub drone_id: i32,
+    /// Trips the drone took off for (Delivered, Finished or Unfinished)
+    pub total_flights: i64,
+    /// Distance flown in km, using the measured distance when available
+    pub total_distance: f64,
+    /// Battery percentage used per km, only from flights with both battery readings
+    pub avg_battery_consumption_per_km: f64,
+    /// Fraction (0 to 1) of completed flights that ended Unfinished
+    pub failure_rate: f64,
+    /// Request time of the latest flight, NULL if the drone never flew
+    pub last_flight: Option<NaiveDateTime>,
+}
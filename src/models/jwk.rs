@@ -0,0 +1,32 @@
+use serde::Serialize;
+
+/// Public key in JSON Web Key format (RFC 7517)
+#[derive(Clone, Serialize, utoipa::ToSchema)]
+pub struct Jwk {
+    /// Key type: RSA or OKP (Ed25519)
+    pub kty: String,
+    /// Key ID, matches the `kid` header of the tokens it signed
+    pub kid: String,
+    /// Algorithm: RS256 or EdDSA
+    pub alg: String,
+    /// Always "sig"
+    #[serde(rename = "use")]
+    pub key_use: String,
+    /// RSA modulus, base64url encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// RSA public exponent, base64url encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    /// Curve of OKP keys: Ed25519
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// Ed25519 public key, base64url encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+}
+
+#[derive(Clone, Serialize, utoipa::ToSchema)]
+pub struct Jwks {
+    pub keys: Vec<Jwk>,
+}
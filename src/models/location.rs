@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
-#[derive(Deserialize, utoipa::ToSchema)]
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct SetLocationRequest {
+    #[validate(length(max = 255))]
     pub name: Option<String>,
+    #[validate(range(min = -90.0, max = 90.0))]
     pub latitude: f64,
+    #[validate(range(min = -180.0, max = 180.0))]
     pub longitude: f64,
 }
 
@@ -16,3 +20,15 @@ pub struct SetLocationResponse {
     pub longitude: String,
     pub message: String,
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct Location {
+    /// Location ID
+    pub id: i32,
+    /// Name of the location
+    pub name: Option<String>,
+    /// Latitude in decimal degrees
+    pub latitude: f64,
+    /// Longitude in decimal degrees
+    pub longitude: f64,
+}
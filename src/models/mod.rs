@@ -1,8 +1,12 @@
 pub mod business;
 pub mod drone;
+pub mod jwk;
 pub mod location;
 pub mod order;
 pub mod product;
+pub mod report;
 pub mod stats;
+pub mod telemetry;
 pub mod trip;
 pub mod user;
+pub mod zone;
@@ -1,13 +1,17 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
-#[derive(Deserialize, utoipa::ToSchema)]
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct OrderDetailRequest {
     pub product_id: i32,
+    #[validate(range(min = 1, max = 1000))]
     pub amount: i32,
 }
 
-#[derive(Deserialize, utoipa::ToSchema)]
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct RegisterOrderRequest {
+    #[validate(length(min = 1, max = 100), nested)]
     pub order_details: Vec<OrderDetailRequest>,
 }
 
@@ -19,3 +23,101 @@ pub struct RegisterOrderResponse {
     pub approved: bool,
     pub message: String,
 }
+
+/// Order states as defined in the class diagram
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum OrderState {
+    Requested,
+    Finished,
+    Canceled,
+    Uncomplete,
+}
+
+impl OrderState {
+    /// Returns the value stored in the `orders.state` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderState::Requested => "Requested",
+            OrderState::Finished => "Finished",
+            OrderState::Canceled => "Canceled",
+            OrderState::Uncomplete => "Uncomplete",
+        }
+    }
+
+    /// Whether an order can move from `self` to `next`
+    ///
+    /// Only 'Requested' orders can change state, every other state is final.
+    pub fn can_transition_to(&self, next: OrderState) -> bool {
+        *self == OrderState::Requested && next != OrderState::Requested
+    }
+}
+
+impl std::str::FromStr for OrderState {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "Requested" => Ok(OrderState::Requested),
+            "Finished" => Ok(OrderState::Finished),
+            "Canceled" => Ok(OrderState::Canceled),
+            "Uncomplete" => Ok(OrderState::Uncomplete),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct OrderTransitionResponse {
+    pub order_id: i32,
+    pub state: OrderState,
+    pub approved: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct Order {
+    /// Order ID
+    pub id: i32,
+    /// Flight number assigned to the order
+    pub flight_number: String,
+    /// When the order was placed
+    pub date: NaiveDateTime,
+    /// Current state of the order
+    pub state: String,
+    /// Total price of the order
+    pub total_price: f64,
+    /// Whether the business approved the order
+    pub approved: i8,
+    /// Customer user ID
+    pub user_id: i32,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct OrderDetail {
+    /// Product ID
+    pub product_id: i32,
+    /// Name of the product when the order was placed
+    pub product_name: String,
+    /// Number of units ordered
+    pub amount: i32,
+    /// Unit price when the order was placed
+    pub price: f64,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct OrderWithDetails {
+    #[serde(flatten)]
+    pub order: Order,
+    pub order_details: Vec<OrderDetail>,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OrderFilter {
+    /// Only return orders in this state
+    pub state: Option<OrderState>,
+    /// Only return orders placed at or after this date
+    pub from: Option<NaiveDateTime>,
+    /// Only return orders placed at or before this date
+    pub to: Option<NaiveDateTime>,
+}
@@ -1,9 +1,15 @@
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
-#[derive(Deserialize, utoipa::ToSchema)]
+use crate::middleware::validation::not_blank;
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct RegisterProductRequest {
+    #[validate(length(min = 1, max = 100), custom(function = "not_blank"))]
     pub name: String,
+    #[validate(length(max = 1000))]
     pub description: String,
+    #[validate(range(exclusive_min = 0.0))]
     pub price: f64,
     pub business_id: i32,
 }
@@ -19,10 +25,13 @@ pub struct RegisterProductResponse {
     pub message: String,
 }
 
-#[derive(Deserialize, utoipa::ToSchema)]
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct UpdateProductRequest {
+    #[validate(length(min = 1, max = 100), custom(function = "not_blank"))]
     pub name: Option<String>,
+    #[validate(length(max = 1000))]
     pub description: Option<String>,
+    #[validate(range(exclusive_min = 0.0))]
     pub price: Option<f64>,
 }
 
@@ -0,0 +1,175 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::middleware::validation::not_blank;
+
+/// Entities a report can be filed against, each one has its own link table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum ReportTarget {
+    Trip,
+    User,
+    Business,
+    Product,
+}
+
+impl ReportTarget {
+    /// Returns the value computed by the report queries for this target
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportTarget::Trip => "Trip",
+            ReportTarget::User => "User",
+            ReportTarget::Business => "Business",
+            ReportTarget::Product => "Product",
+        }
+    }
+}
+
+impl std::str::FromStr for ReportTarget {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "Trip" => Ok(ReportTarget::Trip),
+            "User" => Ok(ReportTarget::User),
+            "Business" => Ok(ReportTarget::Business),
+            "Product" => Ok(ReportTarget::Product),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Report states, reports enter the admin queue as 'Open'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum ReportState {
+    Open,
+    Resolved,
+    Dismissed,
+}
+
+impl ReportState {
+    /// Returns the value stored in the `reports.state` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportState::Open => "Open",
+            ReportState::Resolved => "Resolved",
+            ReportState::Dismissed => "Dismissed",
+        }
+    }
+}
+
+impl std::str::FromStr for ReportState {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "Open" => Ok(ReportState::Open),
+            "Resolved" => Ok(ReportState::Resolved),
+            "Dismissed" => Ok(ReportState::Dismissed),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Actions an admin can take on the target when resolving a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum ModerationAction {
+    /// Deactivate the reported user
+    DeactivateUser,
+    /// Remove the verification of the reported business, sending it back to review
+    UnverifyBusiness,
+    /// Deactivate the reported product
+    DeactivateProduct,
+}
+
+impl ModerationAction {
+    /// The target type the action applies to
+    pub fn target(&self) -> ReportTarget {
+        match self {
+            ModerationAction::DeactivateUser => ReportTarget::User,
+            ModerationAction::UnverifyBusiness => ReportTarget::Business,
+            ModerationAction::DeactivateProduct => ReportTarget::Product,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct RegisterReportRequest {
+    /// Short summary of the report
+    #[validate(length(min = 1, max = 255), custom(function = "not_blank"))]
+    pub title: String,
+    /// Details of the report
+    #[validate(length(min = 1, max = 5000), custom(function = "not_blank"))]
+    pub description: String,
+    /// Type of the reported entity
+    pub target: ReportTarget,
+    /// ID of the reported entity
+    pub target_id: i32,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct RegisterReportResponse {
+    pub report_id: i32,
+    pub state: ReportState,
+    pub message: String,
+}
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct ResolveReportRequest {
+    /// Notes about how the report was handled
+    #[validate(length(max = 5000))]
+    pub resolution: Option<String>,
+    /// Action to take on the reported entity, must match the report target
+    pub action: Option<ModerationAction>,
+}
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct DismissReportRequest {
+    /// Why the report was dismissed
+    #[validate(length(max = 5000))]
+    pub resolution: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ReportTransitionResponse {
+    pub report_id: i32,
+    pub state: ReportState,
+    /// Action taken on the reported entity, if any
+    pub action: Option<ModerationAction>,
+    pub message: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct Report {
+    /// Report ID
+    pub id: i32,
+    /// Short summary of the report
+    pub title: String,
+    /// Details of the report
+    pub description: String,
+    /// User who filed the report
+    pub reporter_id: i32,
+    /// Current state of the report
+    pub state: String,
+    /// Type of the reported entity
+    pub target: String,
+    /// ID of the reported entity
+    pub target_id: i32,
+    /// Notes left by the admin who handled the report
+    pub resolution: Option<String>,
+    /// Admin who handled the report
+    pub resolved_by: Option<i32>,
+    /// When the report was filed
+    pub created_at: NaiveDateTime,
+    /// When the report was resolved or dismissed
+    pub resolved_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReportFilter {
+    /// Only return reports in this state, defaults to 'Open'
+    pub state: Option<ReportState>,
+    /// Only return reports against this type of entity
+    pub target: Option<ReportTarget>,
+}
@@ -1,5 +1,7 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct Stats {
@@ -15,3 +17,73 @@ pub struct Stats {
     pub inactive_accounts: i32,
     pub total_accounts: i32,
 }
+
+/// Size of the buckets returned in a stats series
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl Granularity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Granularity::Day => "day",
+            Granularity::Week => "week",
+            Granularity::Month => "month",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[validate(schema(function = "validate_stats_range"))]
+pub struct StatsQuery {
+    /// First day of the series, defaults to 30 days before `to`
+    pub from: Option<NaiveDate>,
+    /// Last day of the series, defaults to today in the database's time zone
+    pub to: Option<NaiveDate>,
+    /// Bucket size of the series, defaults to day
+    pub granularity: Option<Granularity>,
+}
+
+// Reject ranges that end before they start
+fn validate_stats_range(query: &StatsQuery) -> Result<(), ValidationError> {
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        return Err(
+            ValidationError::new("invalid_range").with_message("from must not be after to".into())
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StatsBucket {
+    /// First day of the bucket (Monday for weeks, the 1st for months)
+    pub period_start: NaiveDate,
+    pub trips: i64,
+    pub finished_trips: i64,
+    pub canceled_trips: i64,
+    pub unfinished_trips: i64,
+    pub avg_delivery_time: f64,
+    pub avg_packing_time: f64,
+    pub avg_battery_consumption_per_km: f64,
+    pub cancellation_rate: f64,
+    pub new_accounts: i64,
+    pub deactivated_accounts: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StatsResponse {
+    #[serde(flatten)]
+    pub stats: Stats,
+    /// Buckets for the requested range, only present when a range or granularity is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<Vec<StatsBucket>>,
+}
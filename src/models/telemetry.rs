@@ -0,0 +1,46 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct TelemetryRequest {
+    /// Drone's current latitude
+    #[validate(range(min = -90.0, max = 90.0))]
+    pub latitude: f64,
+    /// Drone's current longitude
+    #[validate(range(min = -180.0, max = 180.0))]
+    pub longitude: f64,
+    /// Altitude above ground in meters
+    #[validate(range(min = 0.0))]
+    pub altitude: f64,
+    /// Battery level (%)
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub battery: f64,
+    /// When the reading was taken, defaults to now
+    pub timestamp: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct TelemetryResponse {
+    pub telemetry_id: i32,
+    pub trip_id: i32,
+    /// Distance flown so far in km
+    pub real_distance: f64,
+    pub message: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct TelemetryReading {
+    /// Reading ID
+    pub id: i32,
+    /// Latitude in decimal degrees
+    pub latitude: f64,
+    /// Longitude in decimal degrees
+    pub longitude: f64,
+    /// Altitude above ground in meters
+    pub altitude: f64,
+    /// Battery level (%)
+    pub battery: f64,
+    /// When the reading was taken
+    pub recorded_at: NaiveDateTime,
+}
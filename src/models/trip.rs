@@ -1,14 +1,21 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
-#[derive(Deserialize, utoipa::ToSchema)]
+use crate::models::location::Location;
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct RegisterTripRequest {
     /// Weight of the package in kg
+    #[validate(range(exclusive_min = 0.0))]
     pub weight: f64,
     /// Order ID associated with this trip
     pub order_id: i32,
     /// Drone's current latitude (starting point)
+    #[validate(range(min = -90.0, max = 90.0))]
     pub from_latitude: f64,
     /// Drone's current longitude (starting point)
+    #[validate(range(min = -180.0, max = 180.0))]
     pub from_longitude: f64,
     /// Drone ID that will perform the trip
     pub drone_id: i32,
@@ -27,3 +34,150 @@ pub struct RegisterTripResponse {
     pub drone_id: i32,
     pub message: String,
 }
+
+/// Trip states as defined in the Trip State Diagram
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum TripState {
+    Requested,
+    Delivered,
+    Finished,
+    Canceled,
+    Unfinished,
+}
+
+impl TripState {
+    /// Returns the value stored in the `trips.state` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TripState::Requested => "Requested",
+            TripState::Delivered => "Delivered",
+            TripState::Finished => "Finished",
+            TripState::Canceled => "Canceled",
+            TripState::Unfinished => "Unfinished",
+        }
+    }
+
+    /// Whether the state diagram allows moving from `self` to `next`
+    pub fn can_transition_to(&self, next: TripState) -> bool {
+        matches!(
+            (self, next),
+            (TripState::Requested, TripState::Delivered)
+                | (TripState::Requested, TripState::Canceled)
+                | (TripState::Requested, TripState::Unfinished)
+                | (TripState::Delivered, TripState::Finished)
+                | (TripState::Delivered, TripState::Unfinished)
+        )
+    }
+}
+
+impl std::str::FromStr for TripState {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "Requested" => Ok(TripState::Requested),
+            "Delivered" => Ok(TripState::Delivered),
+            "Finished" => Ok(TripState::Finished),
+            "Canceled" => Ok(TripState::Canceled),
+            "Unfinished" => Ok(TripState::Unfinished),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct DeliverTripRequest {
+    /// Minutes the business took to pack the order
+    #[validate(range(min = 0.0))]
+    pub packing_time: Option<f64>,
+    /// Battery level (%) when the drone took off, defaults to the first telemetry reading
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub battery_init: Option<f64>,
+    /// Battery level (%) when the drone reached the destination, defaults to the latest
+    /// telemetry reading
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub battery_arrival: Option<f64>,
+}
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct FinishTripRequest {
+    /// Battery level (%) when the drone got back, defaults to the latest telemetry reading
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub battery_end: Option<f64>,
+    /// Distance actually flown in km, defaults to the distance covered by telemetry
+    #[validate(range(min = 0.0))]
+    pub real_distance: Option<f64>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct TripTransitionResponse {
+    pub trip_id: i32,
+    pub state: TripState,
+    pub message: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct Trip {
+    /// Trip ID
+    pub id: i32,
+    /// When the trip was requested
+    pub request_time: NaiveDateTime,
+    /// Current state of the trip
+    pub state: TripState,
+    /// Weight of the package in kg
+    pub weight: f64,
+    /// Great-circle distance in km
+    pub distance: f64,
+    /// Estimated flight time in minutes
+    pub est_time: f64,
+    /// Minutes from request to arrival at the destination
+    pub arrival_time: Option<f64>,
+    /// Minutes from request to the end of the trip
+    pub total_time: Option<f64>,
+    /// Distance actually flown in km
+    pub real_distance: Option<f64>,
+    /// Order ID associated with this trip
+    pub order_id: i32,
+    /// Drone ID performing the trip
+    pub drone_id: i32,
+    /// Where the drone started
+    pub from_location: Location,
+    /// Where the trip is headed
+    pub to_location: Location,
+}
+
+/// Live update about a trip, pushed to subscribers of the trip's event stream
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TripEvent {
+    /// The trip moved to a new state
+    StateChanged { trip_id: i32, state: TripState },
+    /// The drone reported a new telemetry reading
+    Telemetry {
+        trip_id: i32,
+        latitude: f64,
+        longitude: f64,
+        altitude: f64,
+        battery: f64,
+        real_distance: f64,
+    },
+}
+
+impl TripEvent {
+    /// ID of the trip the event belongs to
+    pub fn trip_id(&self) -> i32 {
+        match self {
+            TripEvent::StateChanged { trip_id, .. } | TripEvent::Telemetry { trip_id, .. } => {
+                *trip_id
+            }
+        }
+    }
+
+    /// Name used as the SSE event type
+    pub fn name(&self) -> &'static str {
+        match self {
+            TripEvent::StateChanged { .. } => "state_changed",
+            TripEvent::Telemetry { .. } => "telemetry",
+        }
+    }
+}
@@ -1,18 +1,43 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
-#[derive(Deserialize, utoipa::ToSchema)]
+use crate::middleware::validation::not_blank;
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct LoginRequest {
+    #[validate(length(min = 1))]
     pub username: String,
+    #[validate(length(min = 1))]
     pub password: String,
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
 pub struct AuthResponse {
     pub token: String,
+    /// Single-use token to get a new access token from /auth/refresh
+    pub refresh_token: String,
     pub user_id: i32,
     pub username: String,
 }
 
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct RefreshRequest {
+    #[validate(length(min = 1))]
+    pub refresh_token: String,
+}
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct LogoutRequest {
+    /// Refresh token to revoke, every refresh token of the user is revoked when omitted
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct LogoutResponse {
+    pub message: String,
+}
+
 #[derive(Serialize, utoipa::ToSchema)]
 pub struct RegisterResponse {
     pub user_id: i32,
@@ -20,21 +45,44 @@ pub struct RegisterResponse {
     pub message: String,
 }
 
-#[derive(Deserialize, utoipa::ToSchema)]
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct RegisterRequest {
+    #[validate(length(min = 3, max = 50), custom(function = "not_blank"))]
     pub username: String,
+    #[validate(length(min = 1, max = 100), custom(function = "not_blank"))]
     pub name: String,
+    #[validate(length(min = 1, max = 100), custom(function = "not_blank"))]
     pub lastname: String,
+    #[validate(range(min = 1_000_000, max = 999_999_999_999_999))]
     pub phone: i64,
+    #[validate(email, length(max = 255))]
     pub email: String,
+    #[validate(length(min = 8, max = 72))]
     pub password: String,
 }
 
-#[derive(Deserialize, utoipa::ToSchema)]
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
 pub struct UpdateUserRequest {
+    #[validate(length(min = 1, max = 100), custom(function = "not_blank"))]
     pub name: Option<String>,
+    #[validate(length(min = 1, max = 100), custom(function = "not_blank"))]
     pub lastname: Option<String>,
+    #[validate(range(min = 1_000_000, max = 999_999_999_999_999))]
     pub phone: Option<i64>,
+    #[validate(email, length(max = 255))]
     pub email: Option<String>,
+    #[validate(length(min = 8, max = 72))]
     pub password: Option<String>,
 }
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct UserStats {
+    /// User ID
+    pub user_id: i32,
+    /// Trips delivering the user's orders
+    pub trips: i32,
+    /// Trips delivering the user's orders that were canceled
+    pub canceled_trips: i32,
+    /// Last successful login, NULL if the user never logged in
+    pub last_login: Option<NaiveDateTime>,
+}
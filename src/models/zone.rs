@@ -0,0 +1,77 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::middleware::validation::not_blank;
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct RegisterZoneRequest {
+    /// Why the zone is prohibited
+    #[validate(length(min = 1, max = 255), custom(function = "not_blank"))]
+    pub description: String,
+    /// When the zone becomes active, defaults to now
+    pub since: Option<NaiveDateTime>,
+    /// When the zone stops being active, NULL means indefinitely
+    pub to: Option<NaiveDateTime>,
+    /// Radius of the zone in km
+    #[validate(range(exclusive_min = 0.0))]
+    pub radius: f64,
+    /// Optional name for the zone's center location
+    #[validate(length(max = 255))]
+    pub name: Option<String>,
+    /// Latitude of the zone's center
+    #[validate(range(min = -90.0, max = 90.0))]
+    pub latitude: f64,
+    /// Longitude of the zone's center
+    #[validate(range(min = -180.0, max = 180.0))]
+    pub longitude: f64,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct RegisterZoneResponse {
+    pub zone_id: i32,
+    pub location_id: i32,
+    pub message: String,
+}
+
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct UpdateZoneRequest {
+    #[validate(length(min = 1, max = 255), custom(function = "not_blank"))]
+    pub description: Option<String>,
+    pub since: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+    #[validate(range(exclusive_min = 0.0))]
+    pub radius: Option<f64>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct UpdateZoneResponse {
+    pub message: String,
+    pub zone_id: i32,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct DeleteZoneResponse {
+    pub message: String,
+    pub zone_id: i32,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ProhibitedZone {
+    /// Zone ID
+    pub id: i32,
+    /// Why the zone is prohibited
+    pub description: String,
+    /// When the zone becomes active
+    pub since: NaiveDateTime,
+    /// When the zone stops being active
+    pub to: Option<NaiveDateTime>,
+    /// Radius of the zone in km
+    pub radius: f64,
+    /// Location ID of the zone's center
+    pub location_id: i32,
+    /// Latitude of the zone's center
+    pub latitude: f64,
+    /// Longitude of the zone's center
+    pub longitude: f64,
+}
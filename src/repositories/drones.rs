@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+
+/// A drone as needed by the business rules
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DroneRecord {
+    pub id: i32,
+    pub user_id: i32,
+    pub number: i32,
+    pub active: bool,
+    /// Cruise speed in km/h
+    pub cruise_speed: f64,
+}
+
+/// Data access for drones
+#[async_trait]
+pub trait DroneRepo: Send + Sync {
+    /// Find a drone by ID, including deactivated ones
+    async fn find(&self, drone_id: i32) -> Result<Option<DroneRecord>, sqlx::Error>;
+
+    /// Whether any drone, active or not, already uses `number`
+    async fn number_taken(&self, number: i32) -> Result<bool, sqlx::Error>;
+
+    /// Insert an active drone and return its ID
+    async fn create(
+        &self,
+        user_id: i32,
+        name: &str,
+        number: i32,
+        cruise_speed: f64,
+    ) -> Result<i32, sqlx::Error>;
+
+    /// Soft-delete a drone, returns false if it was already inactive or doesn't exist
+    async fn deactivate(&self, drone_id: i32) -> Result<bool, sqlx::Error>;
+}
+
+pub struct MySqlDroneRepo {
+    pool: MySqlPool,
+}
+
+impl MySqlDroneRepo {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DroneRepo for MySqlDroneRepo {
+    async fn find(&self, drone_id: i32) -> Result<Option<DroneRecord>, sqlx::Error> {
+        let drone = sqlx::query!(
+            r#"
+            SELECT id, user_id, number, active, CAST(cruise_speed AS DOUBLE) as "cruise_speed!"
+            FROM drones
+            WHERE id = ?
+            "#,
+            drone_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(drone.map(|drone| DroneRecord {
+            id: drone.id,
+            user_id: drone.user_id,
+            number: drone.number,
+            active: drone.active != 0,
+            cruise_speed: drone.cruise_speed,
+        }))
+    }
+
+    async fn number_taken(&self, number: i32) -> Result<bool, sqlx::Error> {
+        let existing_drone = sqlx::query!("SELECT id FROM drones WHERE number = ?", number)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(existing_drone.is_some())
+    }
+
+    async fn create(
+        &self,
+        user_id: i32,
+        name: &str,
+        number: i32,
+        cruise_speed: f64,
+    ) -> Result<i32, sqlx::Error> {
+        let result = sqlx::query!(
+            "INSERT INTO drones (name, number, cruise_speed, user_id) VALUES (?, ?, ?, ?)",
+            name,
+            number,
+            cruise_speed,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_id() as i32)
+    }
+
+    async fn deactivate(&self, drone_id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE drones SET active = FALSE WHERE id = ? AND active = TRUE",
+            drone_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+}
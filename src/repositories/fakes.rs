@@ -0,0 +1,128 @@
+//! In-memory repositories for unit tests
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use crate::{
+    models::location::Location,
+    repositories::{
+        drones::{DroneRecord, DroneRepo},
+        orders::{CreatedOrder, OrderLine, OrderRecord, OrderRepo, OrderableProduct},
+        users::{UserAccount, UserRepo},
+    },
+};
+
+#[derive(Default)]
+pub struct FakeUsers {
+    pub users: Vec<UserAccount>,
+}
+
+#[async_trait]
+impl UserRepo for FakeUsers {
+    async fn find(&self, user_id: i32) -> Result<Option<UserAccount>, sqlx::Error> {
+        Ok(self.users.iter().find(|user| user.id == user_id).copied())
+    }
+}
+
+#[derive(Default)]
+pub struct FakeDrones {
+    pub drones: Mutex<Vec<DroneRecord>>,
+}
+
+#[async_trait]
+impl DroneRepo for FakeDrones {
+    async fn find(&self, drone_id: i32) -> Result<Option<DroneRecord>, sqlx::Error> {
+        let drones = self.drones.lock().unwrap();
+        Ok(drones.iter().find(|drone| drone.id == drone_id).copied())
+    }
+
+    async fn number_taken(&self, number: i32) -> Result<bool, sqlx::Error> {
+        let drones = self.drones.lock().unwrap();
+        Ok(drones.iter().any(|drone| drone.number == number))
+    }
+
+    async fn create(
+        &self,
+        user_id: i32,
+        _name: &str,
+        number: i32,
+        cruise_speed: f64,
+    ) -> Result<i32, sqlx::Error> {
+        let mut drones = self.drones.lock().unwrap();
+        let id = drones.len() as i32 + 1;
+        drones.push(DroneRecord {
+            id,
+            user_id,
+            number,
+            active: true,
+            cruise_speed,
+        });
+        Ok(id)
+    }
+
+    async fn deactivate(&self, drone_id: i32) -> Result<bool, sqlx::Error> {
+        let mut drones = self.drones.lock().unwrap();
+        match drones
+            .iter_mut()
+            .find(|drone| drone.id == drone_id && drone.active)
+        {
+            Some(drone) => {
+                drone.active = false;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct FakeOrders {
+    pub orders: Vec<OrderRecord>,
+    pub products: Vec<OrderableProduct>,
+    /// Business location of each order, orders missing here have none
+    pub locations: Vec<(i32, Location)>,
+    /// Orders passed to `create`, with their user and total price
+    pub created: Mutex<Vec<(i32, f64, Vec<OrderLine>)>>,
+}
+
+#[async_trait]
+impl OrderRepo for FakeOrders {
+    async fn find(&self, order_id: i32) -> Result<Option<OrderRecord>, sqlx::Error> {
+        Ok(self
+            .orders
+            .iter()
+            .find(|order| order.id == order_id)
+            .copied())
+    }
+
+    async fn find_product(&self, product_id: i32) -> Result<Option<OrderableProduct>, sqlx::Error> {
+        Ok(self
+            .products
+            .iter()
+            .find(|product| product.id == product_id)
+            .cloned())
+    }
+
+    async fn create(
+        &self,
+        user_id: i32,
+        total_price: f64,
+        lines: &[OrderLine],
+    ) -> Result<CreatedOrder, sqlx::Error> {
+        let mut created = self.created.lock().unwrap();
+        created.push((user_id, total_price, lines.to_vec()));
+        Ok(CreatedOrder {
+            order_id: created.len() as i32,
+            flight_number: format!("AA{:03}", created.len()),
+        })
+    }
+
+    async fn business_location(&self, order_id: i32) -> Result<Option<Location>, sqlx::Error> {
+        Ok(self
+            .locations
+            .iter()
+            .find(|(id, _)| *id == order_id)
+            .map(|(_, location)| location.clone()))
+    }
+}
@@ -0,0 +1,7 @@
+//! Data access behind traits, so the business rules in `services` can run against fakes
+
+pub mod drones;
+#[cfg(test)]
+pub mod fakes;
+pub mod orders;
+pub mod users;
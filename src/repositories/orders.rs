@@ -0,0 +1,208 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+
+use crate::{
+    handlers::flight_number::next_flight_number,
+    models::{location::Location, order::OrderState},
+};
+
+/// An order as needed by the business rules
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderRecord {
+    pub id: i32,
+    /// Customer who placed the order
+    pub user_id: i32,
+    pub state: OrderState,
+    pub approved: bool,
+}
+
+/// A product as seen when placing an order
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderableProduct {
+    pub id: i32,
+    pub name: String,
+    pub price: f64,
+    pub active: bool,
+    pub business_id: i32,
+    /// Whether the product's business is verified
+    pub business_verified: bool,
+}
+
+/// A product of a new order, with the name and price it has when the order is placed
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderLine {
+    pub product_id: i32,
+    pub product_name: String,
+    pub amount: i32,
+    pub price: f64,
+}
+
+/// IDs assigned to a new order
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreatedOrder {
+    pub order_id: i32,
+    pub flight_number: String,
+}
+
+/// Data access for orders
+#[async_trait]
+pub trait OrderRepo: Send + Sync {
+    /// Find an order by ID
+    async fn find(&self, order_id: i32) -> Result<Option<OrderRecord>, sqlx::Error>;
+
+    /// Find a product that is about to be ordered
+    async fn find_product(&self, product_id: i32) -> Result<Option<OrderableProduct>, sqlx::Error>;
+
+    /// Insert an order with its products, reserving a unique flight number
+    async fn create(
+        &self,
+        user_id: i32,
+        total_price: f64,
+        lines: &[OrderLine],
+    ) -> Result<CreatedOrder, sqlx::Error>;
+
+    /// Location of the business selling the order's products, None if it has no location
+    ///
+    /// All products in an order belong to the same business, see `services::orders::price_order`.
+    async fn business_location(&self, order_id: i32) -> Result<Option<Location>, sqlx::Error>;
+}
+
+pub struct MySqlOrderRepo {
+    pool: MySqlPool,
+}
+
+impl MySqlOrderRepo {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OrderRepo for MySqlOrderRepo {
+    async fn find(&self, order_id: i32) -> Result<Option<OrderRecord>, sqlx::Error> {
+        let order = sqlx::query!(
+            "SELECT id, user_id, state, approved FROM orders WHERE id = ?",
+            order_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        order
+            .map(|order| {
+                let state: OrderState = order.state.parse().map_err(|_| {
+                    sqlx::Error::Decode(format!("invalid order state {}", order.state).into())
+                })?;
+
+                Ok(OrderRecord {
+                    id: order.id,
+                    user_id: order.user_id,
+                    state,
+                    approved: order.approved != 0,
+                })
+            })
+            .transpose()
+    }
+
+    async fn find_product(&self, product_id: i32) -> Result<Option<OrderableProduct>, sqlx::Error> {
+        let product = sqlx::query!(
+            r#"
+            SELECT
+                p.id, p.name, CAST(p.price AS DOUBLE) as "price!", p.active, p.business_id,
+                b.verified
+            FROM products p
+            JOIN businesses b ON p.business_id = b.id
+            WHERE p.id = ?
+            "#,
+            product_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(product.map(|product| OrderableProduct {
+            id: product.id,
+            name: product.name,
+            price: product.price,
+            active: product.active != 0,
+            business_id: product.business_id,
+            business_verified: product.verified != 0,
+        }))
+    }
+
+    async fn create(
+        &self,
+        user_id: i32,
+        total_price: f64,
+        lines: &[OrderLine],
+    ) -> Result<CreatedOrder, sqlx::Error> {
+        // Start a transaction, so a failed insert also releases the flight number
+        let mut tx = self.pool.begin().await?;
+
+        // Reserve a unique flight_number (format: AA999)
+        let flight_number = next_flight_number(&mut *tx).await?;
+
+        // Insert the order
+        let order_result = sqlx::query!(
+            "INSERT INTO orders (flight_number, total_price, user_id) VALUES (?, ?, ?)",
+            flight_number,
+            total_price,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let order_id = order_result.last_insert_id() as i32;
+
+        // Insert order details
+        for line in lines {
+            sqlx::query!(
+                "INSERT INTO order_details (order_id, product_id, product_name, amount, price) \
+                 VALUES (?, ?, ?, ?, ?)",
+                order_id,
+                line.product_id,
+                line.product_name,
+                line.amount,
+                line.price
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // Commit transaction
+        tx.commit().await?;
+
+        Ok(CreatedOrder {
+            order_id,
+            flight_number,
+        })
+    }
+
+    async fn business_location(&self, order_id: i32) -> Result<Option<Location>, sqlx::Error> {
+        let location = sqlx::query!(
+            r#"
+            SELECT
+                l.id as "id?",
+                l.name,
+                CAST(l.latitude AS DOUBLE) as "latitude?",
+                CAST(l.longitude AS DOUBLE) as "longitude?"
+            FROM order_details od
+            JOIN products p ON od.product_id = p.id
+            JOIN businesses b ON p.business_id = b.id
+            LEFT JOIN locations l ON b.location_id = l.id
+            WHERE od.order_id = ?
+            LIMIT 1
+            "#,
+            order_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(location.and_then(|location| {
+            Some(Location {
+                id: location.id?,
+                name: location.name,
+                latitude: location.latitude?,
+                longitude: location.longitude?,
+            })
+        }))
+    }
+}
@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+
+/// Account status of a user, as needed by the business rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserAccount {
+    pub id: i32,
+    pub active: bool,
+}
+
+/// Data access for users
+#[async_trait]
+pub trait UserRepo: Send + Sync {
+    /// Find a user by ID, including deactivated ones
+    async fn find(&self, user_id: i32) -> Result<Option<UserAccount>, sqlx::Error>;
+}
+
+pub struct MySqlUserRepo {
+    pool: MySqlPool,
+}
+
+impl MySqlUserRepo {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserRepo for MySqlUserRepo {
+    async fn find(&self, user_id: i32) -> Result<Option<UserAccount>, sqlx::Error> {
+        let user = sqlx::query!("SELECT id, active FROM users WHERE id = ?", user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        // MySQL BOOLEAN is stored as i8
+        Ok(user.map(|user| UserAccount {
+            id: user.id,
+            active: user.active != 0,
+        }))
+    }
+}
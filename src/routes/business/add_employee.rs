@@ -0,0 +1,101 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::businesses::{BusinessRole, fetch_business_role},
+    middleware::{
+        rbac::{AuthUser, Authorized, BusinessOwner},
+        validation::ValidatedJson,
+    },
+    models::business::{AddEmployeeRequest, AddEmployeeResponse},
+    routes::users::login::AppState,
+};
+
+/// Add an employee to a business
+///
+/// Only the owner of the business can add employees.
+/// Employees can manage the business' products and view its orders,
+/// but cannot delete the business or manage its staff.
+#[utoipa::path(
+    post,
+    path = "/business/{id}/employees",
+    tag = "Business",
+    params(
+        ("id" = i32, Path, description = "Business database id")
+    ),
+    request_body = AddEmployeeRequest,
+    responses(
+        (status = OK, description = "Employee added successfully", body = AddEmployeeResponse),
+        (status = BAD_REQUEST, description = "The owner cannot be added as an employee", body = ProblemDetails),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner of this business", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Business or user not found, or user inactive", body = ProblemDetails),
+        (status = CONFLICT, description = "User is already an employee of this business", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["business_owner"])
+    )
+)]
+pub async fn add_employee(
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<BusinessOwner>,
+    State(state): State<AppState>,
+    Path(business_id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<AddEmployeeRequest>,
+) -> Result<Json<AddEmployeeResponse>, ApiError> {
+    // Check if the requesting user is the owner of the business
+    let role = fetch_business_role(&state.db, business_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
+
+    if role != BusinessRole::Owner {
+        return Err(ApiError::forbidden(
+            "not_business_owner",
+            "User is not the owner of the business",
+        ));
+    }
+
+    // Find the user to add, who must be active
+    let employee = sqlx::query!(
+        "SELECT id FROM users WHERE username = ? AND active = TRUE",
+        payload.username
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("user_not_found", "User not found"))?;
+
+    if employee.id == user_id {
+        return Err(ApiError::bad_request(
+            "owner_as_employee",
+            "The owner can't be added as an employee",
+        ));
+    }
+
+    // Add the employee, ignoring users that already work for the business
+    let result = sqlx::query!(
+        "INSERT IGNORE INTO business_employees (business_id, user_id) VALUES (?, ?)",
+        business_id,
+        employee.id
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::conflict(
+            "already_employee",
+            "User already works for the business",
+        ));
+    }
+
+    Ok(Json(AddEmployeeResponse {
+        business_id,
+        user_id: employee.id,
+        message: format!(
+            "User {} is now an employee of business {}",
+            payload.username, business_id
+        ),
+    }))
+}
@@ -0,0 +1,144 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::businesses::fetch_business_role,
+    middleware::{
+        rbac::{AuthUser, Role},
+        validation::ValidatedQuery,
+    },
+    models::business::{AnalyticsQuery, BusinessAnalytics, TopProduct},
+    routes::users::login::AppState,
+};
+
+/// Default number of best-selling products returned
+const DEFAULT_TOP_PRODUCTS: u32 = 5;
+
+/// Business analytics endpoint
+///
+/// Aggregates the sales of a business from `order_details` over a date range.
+/// - Only finished orders count as sales
+/// - Revenue is the total of the lines of this business
+/// - Each product is listed once under its current name, its sales before a rename included
+/// - Only the owner or an employee of the business, or an admin, can access this endpoint
+#[utoipa::path(
+    get,
+    path = "/business/{id}/analytics",
+    tag = "Business",
+    params(
+        ("id" = i32, Path, description = "Business ID"),
+        AnalyticsQuery
+    ),
+    responses(
+        (status = OK, description = "Business analytics", body = BusinessAnalytics),
+        (status = BAD_REQUEST, description = "from is after to or top is out of range", body = ProblemDetails),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not staff of this business or an admin", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Business not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn get_business_analytics(
+    user: AuthUser,
+    State(state): State<AppState>,
+    Path(business_id): Path<i32>,
+    ValidatedQuery(query): ValidatedQuery<AnalyticsQuery>,
+) -> Result<Json<BusinessAnalytics>, ApiError> {
+    // Check if the requesting user is staff of the business or an admin
+    let role = fetch_business_role(&state.db, business_id, user.id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
+
+    if !role.is_staff() && !user.has_role(Role::Admin) {
+        return Err(ApiError::forbidden(
+            "not_business_staff",
+            "User is not the owner or an employee of the business",
+        ));
+    }
+
+    // Validate the range
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        return Err(ApiError::bad_request(
+            "invalid_range",
+            "from must not be after to",
+        ));
+    }
+
+    // Aggregate revenue and order count
+    let totals = sqlx::query!(
+        r#"
+        SELECT
+            CAST(COALESCE(SUM(od.price * od.amount), 0) AS DOUBLE) as "revenue!",
+            COUNT(DISTINCT o.id) as order_count
+        FROM orders o
+        JOIN order_details od ON od.order_id = o.id
+        JOIN products p ON od.product_id = p.id
+        WHERE p.business_id = ?
+          AND o.state = 'Finished'
+          AND (? IS NULL OR DATE(o.date) >= ?)
+          AND (? IS NULL OR DATE(o.date) <= ?)
+        "#,
+        business_id,
+        query.from,
+        query.from,
+        query.to,
+        query.to
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    // Rank the best-selling products
+    let top = query.top.unwrap_or(DEFAULT_TOP_PRODUCTS);
+    let top_products = sqlx::query_as!(
+        TopProduct,
+        r#"
+        SELECT
+            p.id as product_id,
+            p.name,
+            CAST(SUM(od.amount) AS SIGNED) as "units_sold!: i64",
+            CAST(SUM(od.price * od.amount) AS DOUBLE) as "revenue!"
+        FROM orders o
+        JOIN order_details od ON od.order_id = o.id
+        JOIN products p ON od.product_id = p.id
+        WHERE p.business_id = ?
+          AND o.state = 'Finished'
+          AND (? IS NULL OR DATE(o.date) >= ?)
+          AND (? IS NULL OR DATE(o.date) <= ?)
+        GROUP BY p.id, p.name
+        ORDER BY units_sold DESC, revenue DESC
+        LIMIT ?
+        "#,
+        business_id,
+        query.from,
+        query.from,
+        query.to,
+        query.to,
+        top
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let average_order_value = if totals.order_count > 0 {
+        totals.revenue / totals.order_count as f64
+    } else {
+        0.0
+    };
+
+    Ok(Json(BusinessAnalytics {
+        business_id,
+        from: query.from,
+        to: query.to,
+        revenue: totals.revenue,
+        order_count: totals.order_count,
+        average_order_value,
+        top_products,
+    }))
+}
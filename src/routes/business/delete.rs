@@ -1,11 +1,12 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::StatusCode,
 };
 
 use crate::{
-    middleware::auth::Claims, models::business::DeleteBusinessResponse,
+    error::{ApiError, ProblemDetails},
+    middleware::rbac::{Authorized, BusinessOwner},
+    models::business::DeleteBusinessResponse,
     routes::users::login::AppState,
 };
 
@@ -23,43 +24,42 @@ use crate::{
     ),
     responses(
         (status = OK, description = "Business deactivated successfully", body = DeleteBusinessResponse),
-        (status = FORBIDDEN, description = "User is not the owner of this business"),
-        (status = NOT_FOUND, description = "Business not found or already inactive"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = FORBIDDEN, description = "User is not the owner of this business", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Business not found or already inactive", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
-        ("jwt" = [])
+        ("jwt" = ["business_owner"])
     )
 )]
 pub async fn delete_business(
-    claims: Claims,
+    Authorized(user, _): Authorized<BusinessOwner>,
     State(state): State<AppState>,
     Path(business_id): Path<i32>,
-) -> Result<Json<DeleteBusinessResponse>, StatusCode> {
-    // Get the requesting user's ID from the JWT claims
-    let requesting_user_id = claims
-        .sub
-        .parse::<i32>()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
+) -> Result<Json<DeleteBusinessResponse>, ApiError> {
     // Verify the business exists and get its owner_id
     let business = sqlx::query!(
         "SELECT id, owner_id, active FROM businesses WHERE id = ?",
         business_id
     )
     .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
+    .await?
+    .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
 
     // Check if the requesting user is the owner of the business
-    if business.owner_id != requesting_user_id {
-        return Err(StatusCode::FORBIDDEN);
+    if business.owner_id != user.id {
+        return Err(ApiError::forbidden(
+            "not_business_owner",
+            "User is not the owner of the business",
+        ));
     }
 
     // Check if business is already inactive
     if business.active == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found(
+            "business_not_found",
+            "Business not found",
+        ));
     }
 
     // Deactivate the business by setting active = FALSE
@@ -68,12 +68,14 @@ pub async fn delete_business(
         business_id
     )
     .execute(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
 
     // Check if any row was affected
     if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found(
+            "business_not_found",
+            "Business not found",
+        ));
     }
 
     Ok(Json(DeleteBusinessResponse {
@@ -1,21 +1,27 @@
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{Json, extract::State};
 
-use crate::{middleware::auth::Claims, models::business::Business, routes::users::login::AppState};
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::auth::Claims,
+    models::business::Business,
+    routes::users::login::AppState,
+};
 
 /// List user's active businesses
 ///
-/// Returns all active businesses owned by the authenticated user.
+/// Returns all active businesses owned by the authenticated user or employing them.
 /// Only users with a valid JWT token can access this endpoint.
 /// The user_id is extracted from the JWT token.
 /// Inactive businesses (active = FALSE) are not included in the response.
+/// The verification status and rejection reason let owners follow the admin review.
 #[utoipa::path(
     get,
     path = "/business/list",
     tag = "Business",
     responses(
         (status = OK, description = "Businesses retrieved successfully", body = Vec<Business>),
-        (status = UNAUTHORIZED, description = "Invalid or missing JWT token"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
         ("jwt" = [])
@@ -24,14 +30,11 @@ use crate::{middleware::auth::Claims, models::business::Business, routes::users:
 pub async fn list_businesses(
     claims: Claims,
     State(state): State<AppState>,
-) -> Result<Json<Vec<Business>>, StatusCode> {
+) -> Result<Json<Vec<Business>>, ApiError> {
     // Extract user_id from JWT claims for security
-    let user_id: i32 = claims
-        .sub
-        .parse()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let user_id: i32 = claims.sub.parse().map_err(|_| ApiError::Internal)?;
 
-    // Query all active businesses for this user
+    // Query all active businesses this user owns or works for
     let businesses = sqlx::query_as!(
         Business,
         r#"
@@ -41,16 +44,22 @@ pub async fn list_businesses(
             description,
             owner_id,
             verified,
+            verification_status,
+            rejection_reason,
             active
         FROM businesses 
-        WHERE owner_id = ? AND active = TRUE
+        WHERE active = TRUE
+          AND (
+            owner_id = ?
+            OR id IN (SELECT business_id FROM business_employees WHERE user_id = ?)
+          )
         ORDER BY created_at DESC
         "#,
+        user_id,
         user_id
     )
     .fetch_all(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
 
     Ok(Json(businesses))
 }
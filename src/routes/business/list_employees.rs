@@ -0,0 +1,73 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::businesses::fetch_business_role,
+    middleware::rbac::{AuthUser, Role},
+    models::business::Employee,
+    routes::users::login::AppState,
+};
+
+/// List the employees of a business
+///
+/// The owner, the employees of the business and admins can access this endpoint.
+#[utoipa::path(
+    get,
+    path = "/business/{id}/employees",
+    tag = "Business",
+    params(
+        ("id" = i32, Path, description = "Business database id")
+    ),
+    responses(
+        (status = OK, description = "Employees retrieved successfully", body = Vec<Employee>),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not staff of this business or an admin", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Business not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn list_employees(
+    user: AuthUser,
+    State(state): State<AppState>,
+    Path(business_id): Path<i32>,
+) -> Result<Json<Vec<Employee>>, ApiError> {
+    // Check if the requesting user is staff of the business or an admin
+    let role = fetch_business_role(&state.db, business_id, user.id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
+
+    if !role.is_staff() && !user.has_role(Role::Admin) {
+        return Err(ApiError::forbidden(
+            "not_business_staff",
+            "User is not the owner or an employee of the business",
+        ));
+    }
+
+    // Query the employees, oldest first
+    let employees = sqlx::query_as!(
+        Employee,
+        r#"
+        SELECT
+            u.id as user_id,
+            u.username,
+            u.name,
+            u.lastname,
+            be.created_at as added_at
+        FROM business_employees be
+        JOIN users u ON be.user_id = u.id
+        WHERE be.business_id = ?
+        ORDER BY be.created_at ASC
+        "#,
+        business_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(employees))
+}
@@ -1,5 +1,12 @@
+pub mod add_employee;
+pub mod analytics;
 pub mod delete;
 pub mod list;
+pub mod list_employees;
+pub mod pending;
 pub mod register;
+pub mod reject;
+pub mod remove_employee;
 pub mod set_location;
 pub mod update;
+pub mod verify;
@@ -0,0 +1,54 @@
+use axum::{Json, extract::State};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::rbac::{Admin, Authorized},
+    models::business::Business,
+    routes::users::login::AppState,
+};
+
+/// List businesses pending verification
+///
+/// - Admin only
+/// - Returns active businesses waiting for review, oldest first
+#[utoipa::path(
+    get,
+    path = "/business/pending",
+    tag = "Business",
+    responses(
+        (status = OK, description = "Pending businesses retrieved successfully", body = Vec<Business>),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not an admin", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["admin"])
+    )
+)]
+pub async fn list_pending_businesses(
+    _: Authorized<Admin>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Business>>, ApiError> {
+    // Query the businesses waiting for review
+    let businesses = sqlx::query_as!(
+        Business,
+        r#"
+        SELECT
+            id,
+            name,
+            description,
+            owner_id,
+            verified,
+            verification_status,
+            rejection_reason,
+            active
+        FROM businesses
+        WHERE verification_status = 'Pending' AND active = TRUE
+        ORDER BY created_at ASC
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(businesses))
+}
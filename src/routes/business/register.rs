@@ -1,8 +1,12 @@
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{Json, extract::State};
 
 use crate::{
-    middleware::auth::Claims,
-    models::business::{RegisterBusinessRequest, RegisterBusinessResponse},
+    error::{ApiError, ProblemDetails},
+    middleware::{
+        rbac::{AuthUser, Authorized, Customer},
+        validation::ValidatedJson,
+    },
+    models::business::{RegisterBusinessRequest, RegisterBusinessResponse, VerificationStatus},
     routes::users::login::AppState,
 };
 
@@ -10,7 +14,7 @@ use crate::{
 ///
 /// Registers a new business in the system. Only active users with a valid JWT token can register businesses.
 /// The business will be associated with the authenticated user (extracted from JWT token) as the owner.
-/// New businesses start with verified = false by default.
+/// New businesses start with verified = false and stay 'Pending' until an admin reviews them.
 #[utoipa::path(
     post,
     path = "/business/register",
@@ -18,40 +22,21 @@ use crate::{
     request_body = RegisterBusinessRequest,
     responses(
         (status = OK, description = "Business registered successfully", body = RegisterBusinessResponse),
-        (status = UNAUTHORIZED, description = "Invalid or missing JWT token"),
-        (status = FORBIDDEN, description = "User is not active"),
-        (status = BAD_REQUEST, description = "Invalid request data"),
-        (status = NOT_FOUND, description = "User not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not active", body = ProblemDetails),
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
-        ("jwt" = [])
+        ("jwt" = ["customer"])
     )
 )]
 pub async fn register_business(
-    claims: Claims,
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<Customer>,
     State(state): State<AppState>,
-    Json(payload): Json<RegisterBusinessRequest>,
-) -> Result<Json<RegisterBusinessResponse>, StatusCode> {
-    // Extract user_id from JWT claims for security
-    let user_id: i32 = claims
-        .sub
-        .parse()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    // Verify that the user exists and is active
-    let user = sqlx::query!("SELECT id, active FROM users WHERE id = ?", user_id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
-
-    // Check if user is active (MySQL BOOLEAN is stored as i8)
-    if user.active == 0 {
-        return Err(StatusCode::FORBIDDEN);
-    }
-
-    // Insert the new business (verified defaults to FALSE in the database)
+    ValidatedJson(payload): ValidatedJson<RegisterBusinessRequest>,
+) -> Result<Json<RegisterBusinessResponse>, ApiError> {
+    // Insert the new business (verified defaults to FALSE and verification_status to 'Pending')
     let result = sqlx::query!(
         "INSERT INTO businesses (name, description, owner_id) VALUES (?, ?, ?)",
         payload.name,
@@ -59,8 +44,7 @@ pub async fn register_business(
         user_id
     )
     .execute(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
 
     let business_id = result.last_insert_id() as i32;
 
@@ -70,6 +54,7 @@ pub async fn register_business(
         description: payload.description,
         owner_id: user_id,
         verified: false,
+        verification_status: VerificationStatus::Pending,
         active: true,
         message: "Business registered successfully".to_string(),
     }))
@@ -0,0 +1,77 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::businesses::{BusinessRole, fetch_business_role},
+    middleware::rbac::{AuthUser, Authorized, BusinessStaff},
+    models::business::RemoveEmployeeResponse,
+    routes::users::login::AppState,
+};
+
+/// Remove an employee from a business
+///
+/// The owner can remove any employee, and employees can remove themselves.
+#[utoipa::path(
+    delete,
+    path = "/business/{id}/employees/{user_id}",
+    tag = "Business",
+    params(
+        ("id" = i32, Path, description = "Business database id"),
+        ("user_id" = i32, Path, description = "User database id of the employee to remove")
+    ),
+    responses(
+        (status = OK, description = "Employee removed successfully", body = RemoveEmployeeResponse),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner of this business or the employee", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Business not found or user is not an employee", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["business_owner", "business_employee"])
+    )
+)]
+pub async fn remove_employee(
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<BusinessStaff>,
+    State(state): State<AppState>,
+    Path((business_id, employee_id)): Path<(i32, i32)>,
+) -> Result<Json<RemoveEmployeeResponse>, ApiError> {
+    // Check if the requesting user is the owner of the business or the employee
+    let role = fetch_business_role(&state.db, business_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
+
+    if role != BusinessRole::Owner && user_id != employee_id {
+        return Err(ApiError::forbidden(
+            "not_business_owner",
+            "User is not the owner of the business or the employee",
+        ));
+    }
+
+    // Remove the employee
+    let result = sqlx::query!(
+        "DELETE FROM business_employees WHERE business_id = ? AND user_id = ?",
+        business_id,
+        employee_id
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::not_found(
+            "employee_not_found",
+            "User is not an employee of the business",
+        ));
+    }
+
+    Ok(Json(RemoveEmployeeResponse {
+        business_id,
+        user_id: employee_id,
+        message: format!(
+            "User {} is no longer an employee of business {}",
+            employee_id, business_id
+        ),
+    }))
+}
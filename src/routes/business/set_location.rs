@@ -1,11 +1,15 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::StatusCode,
 };
 
 use crate::{
-    middleware::auth::Claims,
+    error::{ApiError, ProblemDetails},
+    handlers::businesses::fetch_business_role,
+    middleware::{
+        rbac::{Authorized, BusinessStaff},
+        validation::ValidatedJson,
+    },
     models::location::{SetLocationRequest, SetLocationResponse},
     routes::users::login::AppState,
 };
@@ -13,9 +17,9 @@ use crate::{
 /// Set location for a business
 ///
 /// Creates a new location and assigns it to the specified business.
-/// Only the owner of the business can set its location.
-/// The ownership is verified by checking if the authenticated user (from JWT)
-/// is the owner of the business.
+/// Only the owner or an employee of the business can set its location.
+/// The access is verified by checking if the authenticated user (from JWT)
+/// is the owner or an employee of the business.
 #[utoipa::path(
     post,
     path = "/business/{id}/location",
@@ -26,40 +30,32 @@ use crate::{
     request_body = SetLocationRequest,
     responses(
         (status = OK, description = "Location set successfully", body = SetLocationResponse),
-        (status = UNAUTHORIZED, description = "Invalid or missing JWT token"),
-        (status = FORBIDDEN, description = "User is not the owner of this business"),
-        (status = NOT_FOUND, description = "Business not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner or an employee of this business", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Business not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
-        ("jwt" = [])
+        ("jwt" = ["business_owner", "business_employee"])
     )
 )]
 pub async fn set_business_location(
-    claims: Claims,
+    Authorized(user, _): Authorized<BusinessStaff>,
     State(state): State<AppState>,
     Path(business_id): Path<i32>,
-    Json(payload): Json<SetLocationRequest>,
-) -> Result<Json<SetLocationResponse>, StatusCode> {
-    // Get the requesting user's ID from the JWT claims
-    let requesting_user_id = claims
-        .sub
-        .parse::<i32>()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    ValidatedJson(payload): ValidatedJson<SetLocationRequest>,
+) -> Result<Json<SetLocationResponse>, ApiError> {
+    // Check if the requesting user is the owner or an employee of the business
+    let role = fetch_business_role(&state.db, business_id, user.id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
 
-    // Verify the business exists and get its owner_id
-    let business = sqlx::query!(
-        "SELECT id, owner_id FROM businesses WHERE id = ?",
-        business_id
-    )
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
-
-    // Check if the requesting user is the owner of the business
-    if business.owner_id != requesting_user_id {
-        return Err(StatusCode::FORBIDDEN);
+    if !role.is_staff() {
+        return Err(ApiError::forbidden(
+            "not_business_staff",
+            "User is not the owner or an employee of the business",
+        ));
     }
 
     // Insert the new location
@@ -70,8 +66,7 @@ pub async fn set_business_location(
         payload.longitude
     )
     .execute(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
 
     let location_id = location_result.last_insert_id() as i32;
 
@@ -82,8 +77,7 @@ pub async fn set_business_location(
         business_id
     )
     .execute(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
 
     Ok(Json(SetLocationResponse {
         location_id,
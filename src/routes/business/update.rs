@@ -1,19 +1,24 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::StatusCode,
 };
 
 use crate::{
-    middleware::auth::Claims,
+    error::{ApiError, ProblemDetails},
+    handlers::businesses::fetch_business_role,
+    middleware::{
+        rbac::{Authorized, BusinessStaff},
+        validation::ValidatedJson,
+    },
     models::business::{UpdateBusinessRequest, UpdateBusinessResponse},
     routes::users::login::AppState,
 };
 
 /// Update business endpoint
 ///
-/// Only the owner of the business can update it.
-/// The owner_id is verified against the JWT token.
+/// Only the owner or an employee of the business can update it.
+/// The business staff is verified against the JWT token.
+/// Updating a rejected business sends it back to the admins as 'Pending'.
 #[utoipa::path(
     put,
     path = "/business/{id}",
@@ -24,39 +29,31 @@ use crate::{
     request_body = UpdateBusinessRequest,
     responses(
         (status = OK, description = "Business updated successfully", body = UpdateBusinessResponse),
-        (status = FORBIDDEN, description = "User is not the owner of this business"),
-        (status = NOT_FOUND, description = "Business not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner or an employee of this business", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Business not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
-        ("jwt" = [])
+        ("jwt" = ["business_owner", "business_employee"])
     )
 )]
 pub async fn update_business(
-    claims: Claims,
+    Authorized(user, _): Authorized<BusinessStaff>,
     State(state): State<AppState>,
     Path(business_id): Path<i32>,
-    Json(payload): Json<UpdateBusinessRequest>,
-) -> Result<Json<UpdateBusinessResponse>, StatusCode> {
-    // Get the requesting user's ID from the JWT claims
-    let requesting_user_id = claims
-        .sub
-        .parse::<i32>()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    ValidatedJson(payload): ValidatedJson<UpdateBusinessRequest>,
+) -> Result<Json<UpdateBusinessResponse>, ApiError> {
+    // Check if the requesting user is the owner or an employee of the business
+    let role = fetch_business_role(&state.db, business_id, user.id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
 
-    // Verify the business exists and get its owner_id
-    let business = sqlx::query!(
-        "SELECT id, owner_id FROM businesses WHERE id = ?",
-        business_id
-    )
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
-
-    // Check if the requesting user is the owner of the business
-    if business.owner_id != requesting_user_id {
-        return Err(StatusCode::FORBIDDEN);
+    if !role.is_staff() {
+        return Err(ApiError::forbidden(
+            "not_business_staff",
+            "User is not the owner or an employee of the business",
+        ));
     }
 
     // Build the update query dynamically based on provided fields
@@ -81,6 +78,12 @@ pub async fn update_business(
         }));
     }
 
+    // Resubmit rejected businesses for review
+    updates.push(
+        "verification_status = IF(verification_status = 'Rejected', 'Pending', \
+         verification_status)",
+    );
+
     // Execute the update query
     let query_str = format!("UPDATE businesses SET {} WHERE id = ?", updates.join(", "));
 
@@ -90,10 +93,7 @@ pub async fn update_business(
     }
     query = query.bind(business_id);
 
-    query
-        .execute(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    query.execute(&state.db).await?;
 
     Ok(Json(UpdateBusinessResponse {
         message: format!("Business {} has been updated successfully", business_id),
@@ -0,0 +1,88 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::rbac::{Admin, Authorized},
+    models::business::{VerificationResponse, VerificationStatus},
+    routes::users::login::AppState,
+};
+
+/// Verify a business
+///
+/// - Admin only
+/// - The business must be active and 'Pending'
+/// - Verified businesses can sell their products
+#[utoipa::path(
+    post,
+    path = "/business/{id}/verify",
+    tag = "Business",
+    params(
+        ("id" = i32, Path, description = "Business database id to verify")
+    ),
+    responses(
+        (status = OK, description = "Business verified successfully", body = VerificationResponse),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not an admin", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Business not found or inactive", body = ProblemDetails),
+        (status = CONFLICT, description = "Business is not 'Pending'", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["admin"])
+    )
+)]
+pub async fn verify_business(
+    Authorized(admin, _): Authorized<Admin>,
+    State(state): State<AppState>,
+    Path(business_id): Path<i32>,
+) -> Result<Json<VerificationResponse>, ApiError> {
+    // Verify the business exists and is active
+    let business = sqlx::query!(
+        "SELECT id, active, verification_status FROM businesses WHERE id = ?",
+        business_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
+
+    if business.active == 0 {
+        return Err(ApiError::not_found(
+            "business_not_found",
+            "Business not found",
+        ));
+    }
+
+    if business.verification_status != VerificationStatus::Pending.as_str() {
+        return Err(ApiError::conflict(
+            "not_pending",
+            "Business is not pending verification",
+        ));
+    }
+
+    // Verify the business, guarding on the current status
+    let result = sqlx::query!(
+        "UPDATE businesses SET verified = TRUE, verification_status = 'Verified', \
+         rejection_reason = NULL, reviewed_by = ?, reviewed_at = NOW() WHERE id = ? AND \
+         verification_status = 'Pending'",
+        admin.id,
+        business_id
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::conflict(
+            "not_pending",
+            "Business is not pending verification",
+        ));
+    }
+
+    Ok(Json(VerificationResponse {
+        business_id,
+        verification_status: VerificationStatus::Verified,
+        message: format!("Business {} has been verified", business_id),
+    }))
+}
@@ -1,11 +1,13 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::StatusCode,
 };
 
 use crate::{
-    middleware::auth::Claims, models::drone::DeleteDroneResponse, routes::users::login::AppState,
+    error::{ApiError, ProblemDetails},
+    middleware::auth::Claims,
+    models::drone::DeleteDroneResponse,
+    routes::users::login::AppState,
 };
 
 /// Delete (deactivate) drone endpoint
@@ -23,9 +25,9 @@ use crate::{
     ),
     responses(
         (status = OK, description = "Drone deactivated successfully", body = DeleteDroneResponse),
-        (status = FORBIDDEN, description = "User is not the owner of this drone"),
-        (status = NOT_FOUND, description = "Drone not found or already inactive"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = FORBIDDEN, description = "User is not the owner of this drone", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Drone not found or already inactive", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
         ("jwt" = [])
@@ -35,42 +37,36 @@ pub async fn delete_drone(
     claims: Claims,
     State(state): State<AppState>,
     Path(drone_id): Path<i32>,
-) -> Result<Json<DeleteDroneResponse>, StatusCode> {
+) -> Result<Json<DeleteDroneResponse>, ApiError> {
     // Get the requesting user's ID from the JWT claims
-    let requesting_user_id = claims
-        .sub
-        .parse::<i32>()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let requesting_user_id = claims.sub.parse::<i32>().map_err(|_| ApiError::Internal)?;
 
     // Verify the drone exists and get its user_id and active status
-    let drone = sqlx::query!(
-        "SELECT id, user_id, active FROM drones WHERE id = ?",
-        drone_id
-    )
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
+    let drone = state
+        .drones
+        .find(drone_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("drone_not_found", "Drone not found"))?;
 
     // Check if drone is already inactive
-    if drone.active == 0 {
-        return Err(StatusCode::NOT_FOUND);
+    if !drone.active {
+        return Err(ApiError::not_found("drone_not_found", "Drone not found"));
     }
 
     // Check if the requesting user is the owner of the drone
     if drone.user_id != requesting_user_id {
-        return Err(StatusCode::FORBIDDEN);
+        return Err(ApiError::forbidden(
+            "not_drone_owner",
+            "User is not the owner of the drone",
+        ));
     }
 
     // Deactivate the drone by setting active = FALSE
-    let result = sqlx::query!("UPDATE drones SET active = FALSE WHERE id = ?", drone_id)
-        .execute(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let deactivated = state.drones.deactivate(drone_id).await?;
 
     // Check if any row was affected
-    if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
+    if !deactivated {
+        return Err(ApiError::not_found("drone_not_found", "Drone not found"));
     }
 
     Ok(Json(DeleteDroneResponse {
@@ -1,6 +1,11 @@
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{Json, extract::State};
 
-use crate::{middleware::auth::Claims, models::drone::Drone, routes::users::login::AppState};
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::auth::Claims,
+    models::drone::Drone,
+    routes::users::login::AppState,
+};
 
 /// List user's active drones
 ///
@@ -14,8 +19,8 @@ use crate::{middleware::auth::Claims, models::drone::Drone, routes::users::login
     tag = "Drones",
     responses(
         (status = OK, description = "Drones retrieved successfully", body = Vec<Drone>),
-        (status = UNAUTHORIZED, description = "Invalid or missing JWT token"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
         ("jwt" = [])
@@ -24,12 +29,9 @@ use crate::{middleware::auth::Claims, models::drone::Drone, routes::users::login
 pub async fn list_drones(
     claims: Claims,
     State(state): State<AppState>,
-) -> Result<Json<Vec<Drone>>, StatusCode> {
+) -> Result<Json<Vec<Drone>>, ApiError> {
     // Extract user_id from JWT claims for security
-    let user_id: i32 = claims
-        .sub
-        .parse()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let user_id: i32 = claims.sub.parse().map_err(|_| ApiError::Internal)?;
 
     // Query all active drones for this user
     let drones = sqlx::query_as!(
@@ -39,6 +41,7 @@ pub async fn list_drones(
             id,
             name,
             number,
+            CAST(cruise_speed AS DOUBLE) as "cruise_speed!",
             user_id,
             active
         FROM drones 
@@ -48,8 +51,7 @@ pub async fn list_drones(
         user_id
     )
     .fetch_all(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
 
     Ok(Json(drones))
 }
@@ -0,0 +1,94 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::rbac::AuthUser,
+    models::drone::DroneMetrics,
+    routes::users::login::AppState,
+};
+
+/// Drone metrics endpoint
+///
+/// Computes the fleet health metrics of a drone from its trips.
+/// Requested and Canceled trips never took off, so they don't count as flights.
+/// Only the owner of the drone or an admin can access this endpoint.
+#[utoipa::path(
+    get,
+    path = "/drones/{id}/metrics",
+    tag = "Drones",
+    params(
+        ("id" = i32, Path, description = "Drone database id")
+    ),
+    responses(
+        (status = OK, description = "Drone metrics", body = DroneMetrics),
+        (status = FORBIDDEN, description = "User is not the owner of this drone or an admin", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Drone not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn get_drone_metrics(
+    user: AuthUser,
+    State(state): State<AppState>,
+    Path(drone_id): Path<i32>,
+) -> Result<Json<DroneMetrics>, ApiError> {
+    // Verify the drone exists and get its user_id
+    let drone = state
+        .drones
+        .find(drone_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("drone_not_found", "Drone not found"))?;
+
+    // Check if the requesting user is the owner of the drone or an admin
+    if !user.is_self_or_admin(drone.user_id) {
+        return Err(ApiError::forbidden(
+            "not_drone_owner",
+            "User is not the owner of the drone",
+        ));
+    }
+
+    // Aggregate the drone's flights
+    let metrics = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) as total_flights,
+            CAST(COALESCE(SUM(COALESCE(real_distance, distance)), 0) AS DOUBLE) as "total_distance!",
+            CAST(COALESCE(
+                SUM(CASE
+                    WHEN battery_init IS NOT NULL AND battery_end IS NOT NULL
+                    THEN battery_init - battery_end
+                END)
+                / NULLIF(SUM(CASE
+                    WHEN battery_init IS NOT NULL AND battery_end IS NOT NULL
+                    THEN COALESCE(real_distance, distance)
+                END), 0),
+                0
+            ) AS DOUBLE) as "avg_battery_consumption_per_km!",
+            CAST(COALESCE(
+                SUM(state = 'Unfinished') / NULLIF(SUM(state IN ('Finished', 'Unfinished')), 0),
+                0
+            ) AS DOUBLE) as "failure_rate!",
+            MAX(request_time) as last_flight
+        FROM trips
+        WHERE drone_id = ?
+          AND state IN ('Delivered', 'Finished', 'Unfinished')
+        "#,
+        drone_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(DroneMetrics {
+        drone_id,
+        total_flights: metrics.total_flights,
+        total_distance: metrics.total_distance,
+        avg_battery_consumption_per_km: metrics.avg_battery_consumption_per_km,
+        failure_rate: metrics.failure_rate,
+        last_flight: metrics.last_flight,
+    }))
+}
@@ -1,3 +1,4 @@
 pub mod delete;
 pub mod list_drones;
+pub mod metrics;
 pub mod register;
@@ -1,7 +1,12 @@
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{Json, extract::State};
 
 use crate::{
-    middleware::auth::Claims,
+    error::{ApiError, ProblemDetails},
+    geo::DEFAULT_CRUISE_SPEED_KMH,
+    middleware::{
+        rbac::{AuthUser, Authorized, Customer},
+        validation::ValidatedJson,
+    },
     models::drone::{RegisterDroneRequest, RegisterDroneResponse},
     routes::users::login::AppState,
 };
@@ -17,66 +22,42 @@ use crate::{
     request_body = RegisterDroneRequest,
     responses(
         (status = OK, description = "Drone registered successfully", body = RegisterDroneResponse),
-        (status = UNAUTHORIZED, description = "Invalid or missing JWT token"),
-        (status = FORBIDDEN, description = "User is not active"),
-        (status = BAD_REQUEST, description = "Invalid request data or drone number already exists"),
-        (status = NOT_FOUND, description = "User not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not active", body = ProblemDetails),
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = CONFLICT, description = "Drone number already exists", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
-        ("jwt" = [])
+        ("jwt" = ["customer"])
     )
 )]
 pub async fn register_drone(
-    claims: Claims,
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<Customer>,
     State(state): State<AppState>,
-    Json(payload): Json<RegisterDroneRequest>,
-) -> Result<Json<RegisterDroneResponse>, StatusCode> {
-    // Extract user_id from JWT claims for security
-    let user_id: i32 = claims
-        .sub
-        .parse()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    // Verify that the user exists and is active
-    let user = sqlx::query!("SELECT id, active FROM users WHERE id = ?", user_id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
-
-    // Check if user is active (MySQL BOOLEAN is stored as i8)
-    if user.active == 0 {
-        return Err(StatusCode::FORBIDDEN);
-    }
+    ValidatedJson(payload): ValidatedJson<RegisterDroneRequest>,
+) -> Result<Json<RegisterDroneResponse>, ApiError> {
+    let cruise_speed = payload.cruise_speed.unwrap_or(DEFAULT_CRUISE_SPEED_KMH);
 
     // Check if drone number already exists
-    let existing_drone = sqlx::query!("SELECT id FROM drones WHERE number = ?", payload.number)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    if existing_drone.is_some() {
-        return Err(StatusCode::BAD_REQUEST);
+    if state.drones.number_taken(payload.number).await? {
+        return Err(ApiError::conflict(
+            "drone_number_taken",
+            "A drone with this number already exists",
+        ));
     }
 
     // Insert the new drone
-    let result = sqlx::query!(
-        "INSERT INTO drones (name, number, user_id) VALUES (?, ?, ?)",
-        payload.name,
-        payload.number,
-        user_id
-    )
-    .execute(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    let drone_id = result.last_insert_id() as i32;
+    let drone_id = state
+        .drones
+        .create(user_id, &payload.name, payload.number, cruise_speed)
+        .await?;
 
     Ok(Json(RegisterDroneResponse {
         drone_id,
         name: payload.name,
         number: payload.number,
+        cruise_speed,
         message: "Drone registered successfully".to_string(),
     }))
 }
@@ -2,6 +2,9 @@ pub mod business;
 pub mod drones;
 pub mod orders;
 pub mod product;
+pub mod reports;
 pub mod stats;
+pub mod telemetry;
 pub mod trips;
 pub mod users;
+pub mod zones;
@@ -0,0 +1,142 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::stats::{add_trip_stats, remove_trip_stats},
+    middleware::rbac::{AuthUser, Authorized, Customer},
+    models::{
+        order::{OrderState, OrderTransitionResponse},
+        trip::{TripEvent, TripState},
+    },
+    routes::users::login::AppState,
+};
+
+/// Cancel an order
+///
+/// Only the customer who placed the order can cancel it.
+/// The order must still be 'Requested', and its trip (if any) must not have been delivered yet.
+/// A trip that is still 'Requested' is canceled along with the order.
+#[utoipa::path(
+    post,
+    path = "/orders/{id}/cancel",
+    tag = "Orders",
+    params(
+        ("id" = i32, Path, description = "Order database id to cancel")
+    ),
+    responses(
+        (status = OK, description = "Order canceled successfully", body = OrderTransitionResponse),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User did not place this order", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Order not found", body = ProblemDetails),
+        (status = CONFLICT, description = "Order is no longer 'Requested' or was already delivered", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["customer"])
+    )
+)]
+pub async fn cancel_order(
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<Customer>,
+    State(state): State<AppState>,
+    Path(order_id): Path<i32>,
+) -> Result<Json<OrderTransitionResponse>, ApiError> {
+    // Start a transaction so the order and its trip change together
+    let mut tx = state.db.begin().await?;
+
+    // Verify the order exists, locking it until the transaction ends
+    let order = sqlx::query!(
+        "SELECT id, state, approved, user_id FROM orders WHERE id = ? FOR UPDATE",
+        order_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| ApiError::not_found("order_not_found", "Order not found"))?;
+
+    // Check if the requesting user placed the order
+    if order.user_id != user_id {
+        return Err(ApiError::forbidden(
+            "not_order_customer",
+            "User did not place the order",
+        ));
+    }
+
+    // Check that the order can still be canceled
+    let current: OrderState = order.state.parse().map_err(|_| ApiError::Internal)?;
+
+    if !current.can_transition_to(OrderState::Canceled) {
+        return Err(ApiError::conflict(
+            "invalid_transition",
+            "Order can't be canceled from its current state",
+        ));
+    }
+
+    // An order whose package was already delivered can't be canceled
+    // (locking read, so a concurrent delivery waits for this transaction)
+    let delivered = sqlx::query!(
+        "SELECT COUNT(*) as count FROM trips WHERE order_id = ? AND state IN ('Delivered', \
+         'Finished') FOR UPDATE",
+        order_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if delivered.count != 0 {
+        return Err(ApiError::conflict(
+            "order_delivered",
+            "Order was already delivered",
+        ));
+    }
+
+    // Cancel the order and any trip that hasn't been delivered yet
+    sqlx::query!(
+        "UPDATE orders SET state = 'Canceled' WHERE id = ?",
+        order_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let canceled_trips = sqlx::query!(
+        "SELECT id FROM trips WHERE order_id = ? AND state = 'Requested' FOR UPDATE",
+        order_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    // Take the trips out of the stats, they are added back as canceled below
+    for trip in &canceled_trips {
+        remove_trip_stats(&mut *tx, trip.id).await?;
+    }
+
+    sqlx::query!(
+        "UPDATE trips SET state = 'Canceled', canceled_time = NOW() WHERE order_id = ? AND state \
+         = 'Requested'",
+        order_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    for trip in &canceled_trips {
+        add_trip_stats(&mut *tx, trip.id).await?;
+    }
+
+    // Commit transaction
+    tx.commit().await?;
+
+    for trip in canceled_trips {
+        // Notify live subscribers (sending only fails when nobody is listening)
+        let _ = state.trip_events.send(TripEvent::StateChanged {
+            trip_id: trip.id,
+            state: TripState::Canceled,
+        });
+    }
+
+    Ok(Json(OrderTransitionResponse {
+        order_id,
+        state: OrderState::Canceled,
+        approved: order.approved != 0,
+        message: format!("Order {} has been canceled", order_id),
+    }))
+}
@@ -0,0 +1,116 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::auth::Claims,
+    models::order::{Order, OrderDetail, OrderWithDetails},
+    routes::users::login::AppState,
+};
+
+/// Get an order with its details
+///
+/// Returns the order and every product in it, with the name and price they had
+/// when the order was placed.
+/// Only the customer who placed the order or the owner or an employee of its business can
+/// access it.
+#[utoipa::path(
+    get,
+    path = "/orders/{id}",
+    tag = "Orders",
+    params(
+        ("id" = i32, Path, description = "Order database id")
+    ),
+    responses(
+        (status = OK, description = "Order retrieved successfully", body = OrderWithDetails),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User did not place the order and is not staff of its business", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Order not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn get_order(
+    claims: Claims,
+    State(state): State<AppState>,
+    Path(order_id): Path<i32>,
+) -> Result<Json<OrderWithDetails>, ApiError> {
+    // Extract user_id from JWT claims for security
+    let user_id: i32 = claims.sub.parse().map_err(|_| ApiError::Internal)?;
+
+    // Verify the order exists
+    let order = sqlx::query_as!(
+        Order,
+        r#"
+        SELECT
+            id,
+            flight_number,
+            date,
+            state,
+            CAST(total_price AS DOUBLE) as "total_price!",
+            approved,
+            user_id
+        FROM orders
+        WHERE id = ?
+        "#,
+        order_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("order_not_found", "Order not found"))?;
+
+    // Check if the requesting user placed the order or is staff of its business
+    if order.user_id != user_id {
+        let is_business_staff = sqlx::query!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1
+                FROM order_details od
+                JOIN products p ON od.product_id = p.id
+                JOIN businesses b ON p.business_id = b.id
+                LEFT JOIN business_employees be ON be.business_id = b.id AND be.user_id = ?
+                WHERE od.order_id = ? AND (b.owner_id = ? OR be.user_id IS NOT NULL)
+            ) as is_staff
+            "#,
+            user_id,
+            order_id,
+            user_id
+        )
+        .fetch_one(&state.db)
+        .await?;
+
+        if is_business_staff.is_staff == 0 {
+            return Err(ApiError::forbidden(
+                "order_access_denied",
+                "User did not place the order and is not staff of its business",
+            ));
+        }
+    }
+
+    // Query the order details
+    let order_details = sqlx::query_as!(
+        OrderDetail,
+        r#"
+        SELECT
+            product_id,
+            product_name,
+            amount,
+            CAST(price AS DOUBLE) as "price!"
+        FROM order_details
+        WHERE order_id = ?
+        ORDER BY id ASC
+        "#,
+        order_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(OrderWithDetails {
+        order,
+        order_details,
+    }))
+}
@@ -0,0 +1,56 @@
+use axum::{Json, extract::State};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::auth::Claims,
+    models::order::Order,
+    routes::users::login::AppState,
+};
+
+/// List user's orders
+///
+/// Returns all orders placed by the authenticated user, newest first.
+/// The user_id is extracted from the JWT token.
+#[utoipa::path(
+    get,
+    path = "/orders",
+    tag = "Orders",
+    responses(
+        (status = OK, description = "Orders retrieved successfully", body = Vec<Order>),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn list_orders(
+    claims: Claims,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Order>>, ApiError> {
+    // Extract user_id from JWT claims for security
+    let user_id: i32 = claims.sub.parse().map_err(|_| ApiError::Internal)?;
+
+    // Query all orders placed by this user
+    let orders = sqlx::query_as!(
+        Order,
+        r#"
+        SELECT
+            id,
+            flight_number,
+            date,
+            state,
+            CAST(total_price AS DOUBLE) as "total_price!",
+            approved,
+            user_id
+        FROM orders
+        WHERE user_id = ?
+        ORDER BY date DESC
+        "#,
+        user_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(orders))
+}
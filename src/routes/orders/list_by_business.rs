@@ -0,0 +1,90 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::businesses::fetch_business_role,
+    middleware::rbac::{AuthUser, Authorized, BusinessStaff},
+    models::order::{Order, OrderFilter},
+    routes::users::login::AppState,
+};
+
+/// List orders by business
+///
+/// Returns the orders that include products of a specific business, newest first.
+/// Orders can be filtered by state and by a date range.
+/// Only the owner or an employee of the business can access this endpoint.
+#[utoipa::path(
+    get,
+    path = "/business/{id}/orders",
+    tag = "Orders",
+    params(
+        ("id" = i32, Path, description = "Business ID to list orders from"),
+        OrderFilter
+    ),
+    responses(
+        (status = OK, description = "Orders retrieved successfully", body = Vec<Order>),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner or an employee of this business", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Business not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["business_owner", "business_employee"])
+    )
+)]
+pub async fn list_orders_by_business(
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<BusinessStaff>,
+    State(state): State<AppState>,
+    Path(business_id): Path<i32>,
+    Query(filter): Query<OrderFilter>,
+) -> Result<Json<Vec<Order>>, ApiError> {
+    // Check if the requesting user is the owner or an employee of the business
+    let role = fetch_business_role(&state.db, business_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
+
+    if !role.is_staff() {
+        return Err(ApiError::forbidden(
+            "not_business_staff",
+            "User is not the owner or an employee of the business",
+        ));
+    }
+
+    // Query the business orders, skipping the filters that weren't provided
+    let order_state = filter.state.map(|order_state| order_state.as_str());
+    let orders = sqlx::query_as!(
+        Order,
+        r#"
+        SELECT DISTINCT
+            o.id,
+            o.flight_number,
+            o.date,
+            o.state,
+            CAST(o.total_price AS DOUBLE) as "total_price!",
+            o.approved,
+            o.user_id
+        FROM orders o
+        JOIN order_details od ON od.order_id = o.id
+        JOIN products p ON od.product_id = p.id
+        WHERE p.business_id = ?
+          AND (? IS NULL OR o.state = ?)
+          AND (? IS NULL OR o.date >= ?)
+          AND (? IS NULL OR o.date <= ?)
+        ORDER BY o.date DESC
+        "#,
+        business_id,
+        order_state,
+        order_state,
+        filter.from,
+        filter.from,
+        filter.to,
+        filter.to
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(orders))
+}
@@ -1 +1,7 @@
+pub mod approve;
+pub mod cancel;
+pub mod get;
+pub mod list;
+pub mod list_by_business;
 pub mod register;
+pub mod reject;
@@ -0,0 +1,100 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::rbac::{AuthUser, Authorized, BusinessOwner},
+    models::order::{OrderState, OrderTransitionResponse},
+    routes::users::login::AppState,
+};
+
+/// Reject an order
+///
+/// Only the owner of the business selling the order's products can reject it.
+/// The order must still be 'Requested' and not approved yet.
+/// Rejected orders are moved to 'Canceled'.
+#[utoipa::path(
+    post,
+    path = "/orders/{id}/reject",
+    tag = "Orders",
+    params(
+        ("id" = i32, Path, description = "Order database id to reject")
+    ),
+    responses(
+        (status = OK, description = "Order rejected successfully", body = OrderTransitionResponse),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner of the order's business", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Order not found", body = ProblemDetails),
+        (status = CONFLICT, description = "Order is already approved or no longer 'Requested'", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["business_owner"])
+    )
+)]
+pub async fn reject_order(
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<BusinessOwner>,
+    State(state): State<AppState>,
+    Path(order_id): Path<i32>,
+) -> Result<Json<OrderTransitionResponse>, ApiError> {
+    // Verify the order exists and get the owner of its business
+    // All products in an order belong to the same business, checked when it is placed
+    let order = sqlx::query!(
+        r#"
+        SELECT o.id, o.state, o.approved, b.owner_id
+        FROM orders o
+        JOIN order_details od ON od.order_id = o.id
+        JOIN products p ON od.product_id = p.id
+        JOIN businesses b ON p.business_id = b.id
+        WHERE o.id = ?
+        LIMIT 1
+        "#,
+        order_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("order_not_found", "Order not found"))?;
+
+    // Check if the requesting user is the owner of the business
+    if order.owner_id != user_id {
+        return Err(ApiError::forbidden(
+            "not_business_owner",
+            "User is not the owner of the business",
+        ));
+    }
+
+    // Check that the order can still be rejected
+    let current: OrderState = order.state.parse().map_err(|_| ApiError::Internal)?;
+
+    if !current.can_transition_to(OrderState::Canceled) || order.approved != 0 {
+        return Err(ApiError::conflict(
+            "invalid_transition",
+            "Order can't be rejected from its current state",
+        ));
+    }
+
+    // Cancel the order, guarding on the current state
+    let result = sqlx::query!(
+        "UPDATE orders SET state = 'Canceled' WHERE id = ? AND state = 'Requested' AND approved = \
+         FALSE",
+        order_id
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::conflict(
+            "invalid_transition",
+            "Order can't be rejected from its current state",
+        ));
+    }
+
+    Ok(Json(OrderTransitionResponse {
+        order_id,
+        state: OrderState::Canceled,
+        approved: false,
+        message: format!("Order {} has been rejected", order_id),
+    }))
+}
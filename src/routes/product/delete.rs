@@ -1,20 +1,22 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::StatusCode,
 };
 
 use crate::{
-    middleware::auth::Claims, models::product::DeleteProductResponse,
+    error::{ApiError, ProblemDetails},
+    handlers::businesses::fetch_business_role,
+    middleware::rbac::{Authorized, BusinessStaff},
+    models::product::DeleteProductResponse,
     routes::users::login::AppState,
 };
 
 /// Delete (deactivate) product endpoint
 ///
-/// Only the owner of the business that owns the product can delete it.
+/// Only the owner or an employee of the business that owns the product can delete it.
 /// The product is soft-deleted by setting active = FALSE.
-/// The ownership is verified by checking if the authenticated user (from JWT)
-/// is the owner of the business associated with the product.
+/// The access is verified by checking if the authenticated user (from JWT)
+/// is the owner or an employee of the business associated with the product.
 #[utoipa::path(
     delete,
     path = "/product/{id}",
@@ -24,53 +26,46 @@ use crate::{
     ),
     responses(
         (status = OK, description = "Product deactivated successfully", body = DeleteProductResponse),
-        (status = FORBIDDEN, description = "User is not the owner of the business that owns this product"),
-        (status = NOT_FOUND, description = "Product not found or already inactive"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = FORBIDDEN, description = "User is not the owner or an employee of the business that owns this product", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Product not found or already inactive", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
-        ("jwt" = [])
+        ("jwt" = ["business_owner", "business_employee"])
     )
 )]
 pub async fn delete_product(
-    claims: Claims,
+    Authorized(user, _): Authorized<BusinessStaff>,
     State(state): State<AppState>,
     Path(product_id): Path<i32>,
-) -> Result<Json<DeleteProductResponse>, StatusCode> {
-    // Get the requesting user's ID from the JWT claims
-    let requesting_user_id = claims
-        .sub
-        .parse::<i32>()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
+) -> Result<Json<DeleteProductResponse>, ApiError> {
     // Verify the product exists and get its business_id and active status
     let product = sqlx::query!(
         "SELECT id, business_id, active FROM products WHERE id = ?",
         product_id
     )
     .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
+    .await?
+    .ok_or_else(|| ApiError::not_found("product_not_found", "Product not found"))?;
 
     // Check if product is already inactive
     if product.active == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found(
+            "product_not_found",
+            "Product not found",
+        ));
     }
 
-    // Get the business owner_id
-    let business = sqlx::query!(
-        "SELECT owner_id FROM businesses WHERE id = ?",
-        product.business_id
-    )
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
+    // Check if the requesting user is the owner or an employee of the business
+    let role = fetch_business_role(&state.db, product.business_id, user.id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
 
-    // Check if the requesting user is the owner of the business
-    if business.owner_id != requesting_user_id {
-        return Err(StatusCode::FORBIDDEN);
+    if !role.is_staff() {
+        return Err(ApiError::forbidden(
+            "not_business_staff",
+            "User is not the owner or an employee of the business",
+        ));
     }
 
     // Deactivate the product by setting active = FALSE
@@ -79,12 +74,14 @@ pub async fn delete_product(
         product_id
     )
     .execute(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
 
     // Check if any row was affected
     if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found(
+            "product_not_found",
+            "Product not found",
+        ));
     }
 
     Ok(Json(DeleteProductResponse {
@@ -1,16 +1,21 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::StatusCode,
 };
 
-use crate::{middleware::auth::Claims, models::product::Product, routes::users::login::AppState};
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::businesses::fetch_business_role,
+    middleware::auth::Claims,
+    models::product::Product,
+    routes::users::login::AppState,
+};
 
 /// List products by business
 ///
 /// Returns all active products for a specific business.
-/// Only the owner of the business can access this endpoint.
-/// The user_id is extracted from the JWT token and verified against the business owner_id.
+/// Only the owner or an employee of the business can access this endpoint.
+/// The user_id is extracted from the JWT token and verified against the business staff.
 #[utoipa::path(
     get,
     path = "/product/business/{business_id}",
@@ -20,10 +25,10 @@ use crate::{middleware::auth::Claims, models::product::Product, routes::users::l
     ),
     responses(
         (status = OK, description = "Products retrieved successfully", body = Vec<Product>),
-        (status = UNAUTHORIZED, description = "Invalid or missing JWT token"),
-        (status = FORBIDDEN, description = "User is not the owner of this business"),
-        (status = NOT_FOUND, description = "Business not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner or an employee of this business", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Business not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
         ("jwt" = [])
@@ -33,26 +38,20 @@ pub async fn list_products_by_business(
     claims: Claims,
     State(state): State<AppState>,
     Path(business_id): Path<i32>,
-) -> Result<Json<Vec<Product>>, StatusCode> {
+) -> Result<Json<Vec<Product>>, ApiError> {
     // Extract user_id from JWT claims for security
-    let user_id: i32 = claims
-        .sub
-        .parse()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let user_id: i32 = claims.sub.parse().map_err(|_| ApiError::Internal)?;
 
-    // Verify that the business exists and get its owner_id
-    let business = sqlx::query!(
-        "SELECT id, owner_id FROM businesses WHERE id = ?",
-        business_id
-    )
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
+    // Check if the requesting user is the owner or an employee of the business
+    let role = fetch_business_role(&state.db, business_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
 
-    // Check if the requesting user is the owner of the business
-    if business.owner_id != user_id {
-        return Err(StatusCode::FORBIDDEN);
+    if !role.is_staff() {
+        return Err(ApiError::forbidden(
+            "not_business_staff",
+            "User is not the owner or an employee of the business",
+        ));
     }
 
     // Query all active products for this business
@@ -73,8 +72,7 @@ pub async fn list_products_by_business(
         business_id
     )
     .fetch_all(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
 
     Ok(Json(products))
 }
@@ -1,16 +1,21 @@
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{Json, extract::State};
 
 use crate::{
-    middleware::auth::Claims,
+    error::{ApiError, ProblemDetails},
+    handlers::businesses::fetch_business_role,
+    middleware::{
+        rbac::{AuthUser, Authorized, BusinessStaff},
+        validation::ValidatedJson,
+    },
     models::product::{RegisterProductRequest, RegisterProductResponse},
     routes::users::login::AppState,
 };
 
 /// Register a new product
 ///
-/// Registers a new product in a business. Only the owner of the business can register products.
-/// The business_id is provided in the request, and the system verifies that the authenticated user
-/// (from JWT) is the owner of that business before allowing the product registration.
+/// Registers a new product in a business. Only the owner or an employee of the business can
+/// register products. The business_id is provided in the request, and the system verifies that the
+/// authenticated user (from JWT) is staff of that business before allowing the product registration.
 #[utoipa::path(
     post,
     path = "/product/register",
@@ -18,40 +23,31 @@ use crate::{
     request_body = RegisterProductRequest,
     responses(
         (status = OK, description = "Product registered successfully", body = RegisterProductResponse),
-        (status = UNAUTHORIZED, description = "Invalid or missing JWT token"),
-        (status = FORBIDDEN, description = "User is not the owner of the specified business"),
-        (status = BAD_REQUEST, description = "Invalid request data"),
-        (status = NOT_FOUND, description = "Business not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner or an employee of the specified business", body = ProblemDetails),
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Business not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
-        ("jwt" = [])
+        ("jwt" = ["business_owner", "business_employee"])
     )
 )]
 pub async fn register_product(
-    claims: Claims,
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<BusinessStaff>,
     State(state): State<AppState>,
-    Json(payload): Json<RegisterProductRequest>,
-) -> Result<Json<RegisterProductResponse>, StatusCode> {
-    // Extract user_id from JWT claims for security
-    let user_id: i32 = claims
-        .sub
-        .parse()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    ValidatedJson(payload): ValidatedJson<RegisterProductRequest>,
+) -> Result<Json<RegisterProductResponse>, ApiError> {
+    // Check if the requesting user is the owner or an employee of the business
+    let role = fetch_business_role(&state.db, payload.business_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
 
-    // Verify that the business exists and get its owner_id
-    let business = sqlx::query!(
-        "SELECT id, owner_id FROM businesses WHERE id = ?",
-        payload.business_id
-    )
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
-
-    // Check if the requesting user is the owner of the business
-    if business.owner_id != user_id {
-        return Err(StatusCode::FORBIDDEN);
+    if !role.is_staff() {
+        return Err(ApiError::forbidden(
+            "not_business_staff",
+            "User is not the owner or an employee of the business",
+        ));
     }
 
     // Insert the new product (active defaults to TRUE in the database)
@@ -63,8 +59,7 @@ pub async fn register_product(
         payload.business_id
     )
     .execute(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
 
     let product_id = result.last_insert_id() as i32;
 
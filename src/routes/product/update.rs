@@ -1,20 +1,24 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::StatusCode,
 };
 
 use crate::{
-    middleware::auth::Claims,
+    error::{ApiError, ProblemDetails},
+    handlers::businesses::fetch_business_role,
+    middleware::{
+        rbac::{Authorized, BusinessStaff},
+        validation::ValidatedJson,
+    },
     models::product::{UpdateProductRequest, UpdateProductResponse},
     routes::users::login::AppState,
 };
 
 /// Update product endpoint
 ///
-/// Only the owner of the business that owns the product can update it.
-/// The ownership is verified by checking if the authenticated user (from JWT)
-/// is the owner of the business associated with the product.
+/// Only the owner or an employee of the business that owns the product can update it.
+/// The access is verified by checking if the authenticated user (from JWT)
+/// is the owner or an employee of the business associated with the product.
 #[utoipa::path(
     put,
     path = "/product/{id}",
@@ -25,49 +29,40 @@ use crate::{
     request_body = UpdateProductRequest,
     responses(
         (status = OK, description = "Product updated successfully", body = UpdateProductResponse),
-        (status = FORBIDDEN, description = "User is not the owner of the business that owns this product"),
-        (status = NOT_FOUND, description = "Product not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner or an employee of the business that owns this product", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Product not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
-        ("jwt" = [])
+        ("jwt" = ["business_owner", "business_employee"])
     )
 )]
 pub async fn update_product(
-    claims: Claims,
+    Authorized(user, _): Authorized<BusinessStaff>,
     State(state): State<AppState>,
     Path(product_id): Path<i32>,
-    Json(payload): Json<UpdateProductRequest>,
-) -> Result<Json<UpdateProductResponse>, StatusCode> {
-    // Get the requesting user's ID from the JWT claims
-    let requesting_user_id = claims
-        .sub
-        .parse::<i32>()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
+    ValidatedJson(payload): ValidatedJson<UpdateProductRequest>,
+) -> Result<Json<UpdateProductResponse>, ApiError> {
     // Verify the product exists and get its business_id
     let product = sqlx::query!(
         "SELECT id, business_id FROM products WHERE id = ?",
         product_id
     )
     .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
+    .await?
+    .ok_or_else(|| ApiError::not_found("product_not_found", "Product not found"))?;
 
-    // Get the business owner_id
-    let business = sqlx::query!(
-        "SELECT owner_id FROM businesses WHERE id = ?",
-        product.business_id
-    )
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
+    // Check if the requesting user is the owner or an employee of the business
+    let role = fetch_business_role(&state.db, product.business_id, user.id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("business_not_found", "Business not found"))?;
 
-    // Check if the requesting user is the owner of the business
-    if business.owner_id != requesting_user_id {
-        return Err(StatusCode::FORBIDDEN);
+    if !role.is_staff() {
+        return Err(ApiError::forbidden(
+            "not_business_staff",
+            "User is not the owner or an employee of the business",
+        ));
     }
 
     // Build the update query dynamically based on provided fields
@@ -106,10 +101,7 @@ pub async fn update_product(
     }
     query = query.bind(product_id);
 
-    query
-        .execute(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    query.execute(&state.db).await?;
 
     Ok(Json(UpdateProductResponse {
         message: format!("Product {} has been updated successfully", product_id),
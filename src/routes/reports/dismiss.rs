@@ -0,0 +1,85 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::{
+        rbac::{Admin, Authorized},
+        validation::ValidatedJson,
+    },
+    models::report::{DismissReportRequest, ReportState, ReportTransitionResponse},
+    routes::users::login::AppState,
+};
+
+/// Dismiss a report
+///
+/// - Admin only
+/// - The report must still be 'Open'
+/// - Nothing is done to the reported entity
+#[utoipa::path(
+    post,
+    path = "/reports/{id}/dismiss",
+    tag = "Reports",
+    params(
+        ("id" = i32, Path, description = "Report database id to dismiss")
+    ),
+    request_body = DismissReportRequest,
+    responses(
+        (status = OK, description = "Report dismissed successfully", body = ReportTransitionResponse),
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not an admin", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Report not found", body = ProblemDetails),
+        (status = CONFLICT, description = "Report is no longer 'Open'", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["admin"])
+    )
+)]
+pub async fn dismiss_report(
+    Authorized(admin, _): Authorized<Admin>,
+    State(state): State<AppState>,
+    Path(report_id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<DismissReportRequest>,
+) -> Result<Json<ReportTransitionResponse>, ApiError> {
+    // Verify the report exists
+    let report = sqlx::query!("SELECT id, state FROM reports WHERE id = ?", report_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("report_not_found", "Report not found"))?;
+
+    if report.state != ReportState::Open.as_str() {
+        return Err(ApiError::conflict(
+            "report_closed",
+            "Report is no longer open",
+        ));
+    }
+
+    // Dismiss the report, guarding on the current state
+    let result = sqlx::query!(
+        "UPDATE reports SET state = 'Dismissed', resolution = ?, resolved_by = ?, resolved_at = \
+         NOW() WHERE id = ? AND state = 'Open'",
+        payload.resolution,
+        admin.id,
+        report_id
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::conflict(
+            "report_closed",
+            "Report is no longer open",
+        ));
+    }
+
+    Ok(Json(ReportTransitionResponse {
+        report_id,
+        state: ReportState::Dismissed,
+        action: None,
+        message: format!("Report {} has been dismissed", report_id),
+    }))
+}
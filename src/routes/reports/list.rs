@@ -0,0 +1,44 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::reports::fetch_reports,
+    middleware::rbac::{Admin, Authorized},
+    models::report::{Report, ReportFilter, ReportState},
+    routes::users::login::AppState,
+};
+
+/// List reports (moderation queue)
+///
+/// - Admin only
+/// - Returns the 'Open' reports unless another state is requested, oldest first
+#[utoipa::path(
+    get,
+    path = "/reports",
+    tag = "Reports",
+    params(ReportFilter),
+    responses(
+        (status = OK, description = "Reports retrieved successfully", body = Vec<Report>),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not an admin", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["admin"])
+    )
+)]
+pub async fn list_reports(
+    _: Authorized<Admin>,
+    State(state): State<AppState>,
+    Query(filter): Query<ReportFilter>,
+) -> Result<Json<Vec<Report>>, ApiError> {
+    // Query the reports, skipping the filters that weren't provided
+    let report_state = filter.state.unwrap_or(ReportState::Open);
+    let target = filter.target.map(|target| target.as_str());
+    let reports = fetch_reports(&state.db, report_state.as_str(), target).await?;
+
+    Ok(Json(reports))
+}
@@ -0,0 +1,4 @@
+pub mod dismiss;
+pub mod list;
+pub mod register;
+pub mod resolve;
@@ -0,0 +1,76 @@
+use axum::{Json, extract::State};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::reports::{link_report, report_target_exists},
+    middleware::{
+        rbac::{AuthUser, Authorized, Customer},
+        validation::ValidatedJson,
+    },
+    models::report::{RegisterReportRequest, RegisterReportResponse, ReportState},
+    routes::users::login::AppState,
+};
+
+/// File a report
+///
+/// Any authenticated user can report a trip, user, business or product.
+/// The report enters the admin queue as 'Open'.
+#[utoipa::path(
+    post,
+    path = "/reports",
+    tag = "Reports",
+    request_body = RegisterReportRequest,
+    responses(
+        (status = OK, description = "Report filed successfully", body = RegisterReportResponse),
+        (status = BAD_REQUEST, description = "Title or description is empty", body = ProblemDetails),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not active", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Reported entity not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["customer"])
+    )
+)]
+pub async fn register_report(
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<Customer>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<RegisterReportRequest>,
+) -> Result<Json<RegisterReportResponse>, ApiError> {
+    // Start a transaction
+    let mut tx = state.db.begin().await?;
+
+    // Verify the reported entity exists
+    let exists = report_target_exists(&mut *tx, payload.target, payload.target_id).await?;
+
+    if !exists {
+        return Err(ApiError::not_found(
+            "target_not_found",
+            "Reported entity not found",
+        ));
+    }
+
+    // Insert the report
+    let result = sqlx::query!(
+        "INSERT INTO reports (title, description, reporter_id) VALUES (?, ?, ?)",
+        payload.title,
+        payload.description,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let report_id = result.last_insert_id() as i32;
+
+    // Link the report to the reported entity
+    link_report(&mut *tx, report_id, payload.target, payload.target_id).await?;
+
+    // Commit the transaction
+    tx.commit().await?;
+
+    Ok(Json(RegisterReportResponse {
+        report_id,
+        state: ReportState::Open,
+        message: "Report filed successfully".to_string(),
+    }))
+}
@@ -0,0 +1,124 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::{
+        reports::{apply_moderation_action, fetch_report_for_update},
+        stats::deactivate_user_stats,
+    },
+    middleware::{
+        rbac::{Admin, Authorized},
+        validation::ValidatedJson,
+    },
+    models::report::{
+        ModerationAction, ReportState, ReportTarget, ReportTransitionResponse, ResolveReportRequest,
+    },
+    routes::users::login::AppState,
+};
+
+/// Resolve a report
+///
+/// - Admin only
+/// - The report must still be 'Open'
+/// - An optional action is applied to the reported entity in the same transaction:
+///   deactivate a user, unverify a business or deactivate a product. The action must match
+///   the type of the reported entity.
+#[utoipa::path(
+    post,
+    path = "/reports/{id}/resolve",
+    tag = "Reports",
+    params(
+        ("id" = i32, Path, description = "Report database id to resolve")
+    ),
+    request_body = ResolveReportRequest,
+    responses(
+        (status = OK, description = "Report resolved successfully", body = ReportTransitionResponse),
+        (status = BAD_REQUEST, description = "Action doesn't apply to the reported entity", body = ProblemDetails),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not an admin", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Report not found", body = ProblemDetails),
+        (status = CONFLICT, description = "Report is no longer 'Open'", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["admin"])
+    )
+)]
+pub async fn resolve_report(
+    Authorized(admin, _): Authorized<Admin>,
+    State(state): State<AppState>,
+    Path(report_id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<ResolveReportRequest>,
+) -> Result<Json<ReportTransitionResponse>, ApiError> {
+    // Start a transaction
+    let mut tx = state.db.begin().await?;
+
+    // Lock the report and resolve its target
+    let report = fetch_report_for_update(&mut *tx, report_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("report_not_found", "Report not found"))?;
+
+    let current: ReportState = report.state.parse().map_err(|_| ApiError::Internal)?;
+
+    if current != ReportState::Open {
+        return Err(ApiError::conflict(
+            "report_closed",
+            "Report is no longer open",
+        ));
+    }
+
+    // Check that the action applies to the reported entity
+    let target: ReportTarget = report.target.parse().map_err(|_| ApiError::Internal)?;
+
+    if let Some(action) = payload.action
+        && action.target() != target
+    {
+        return Err(ApiError::bad_request(
+            "action_target_mismatch",
+            "Action doesn't apply to the reported entity",
+        ));
+    }
+
+    // Act on the reported entity
+    let mut user_deactivated = false;
+    if let Some(action) = payload.action {
+        let changed = apply_moderation_action(&mut *tx, action, report.target_id).await?;
+        user_deactivated = changed && action == ModerationAction::DeactivateUser;
+    }
+
+    // Resolve the report, guarding on the current state
+    let result = sqlx::query!(
+        "UPDATE reports SET state = 'Resolved', resolution = ?, resolved_by = ?, resolved_at = \
+         NOW() WHERE id = ? AND state = 'Open'",
+        payload.resolution,
+        admin.id,
+        report_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::conflict(
+            "report_closed",
+            "Report is no longer open",
+        ));
+    }
+
+    // Commit the transaction
+    tx.commit().await?;
+
+    // Update stats: decrement active_accounts and increment inactive_accounts
+    if user_deactivated {
+        deactivate_user_stats(&state.db).await?;
+    }
+
+    Ok(Json(ReportTransitionResponse {
+        report_id,
+        state: ReportState::Resolved,
+        action: payload.action,
+        message: format!("Report {} has been resolved", report_id),
+    }))
+}
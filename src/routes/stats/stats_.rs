@@ -1,44 +1,158 @@
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{Json, extract::State};
+use chrono::Duration;
 
-use crate::{models::stats::Stats, routes::users::login::AppState};
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::validation::ValidatedQuery,
+    models::stats::{Stats, StatsBucket, StatsQuery, StatsResponse},
+    routes::users::login::AppState,
+};
+
+/// Days covered by a series when only `to` (or nothing but the granularity) is given
+const DEFAULT_SERIES_DAYS: i64 = 30;
 
 /// Get global statistics
 ///
 /// Returns the global statistics including trip counts, averages, and account information.
+/// The trip counts and averages are computed from the daily buckets when the request is made,
+/// with weekend_trips covering this week.
+/// - avg_delivery_time and avg_packing_time are in minutes
+/// - avg_battery_consumption_per_km only uses trips with both battery readings
+/// - cancellation_rate is the fraction (0 to 1) of trips that were canceled
+/// When from, to or granularity are given, the response also includes a series with one
+/// bucket per day, week or month in that range.
 /// This is a public endpoint that doesn't require authentication.
 #[utoipa::path(
     get,
     path = "/stats",
     tag = "Stats",
+    params(StatsQuery),
     responses(
-        (status = OK, description = "Statistics retrieved successfully", body = Stats),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = OK, description = "Statistics retrieved successfully", body = StatsResponse),
+        (status = BAD_REQUEST, description = "from is after to", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     )
 )]
-pub async fn get_stats(State(state): State<AppState>) -> Result<Json<Stats>, StatusCode> {
-    // Query the stats table (should only have one row with id = 1)
+pub async fn get_stats(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<StatsQuery>,
+) -> Result<Json<StatsResponse>, ApiError> {
+    // Read the account counters from the stats row (id = 1) and merge every daily bucket into
+    // the trip counters and averages
     let stats = sqlx::query_as!(
         Stats,
         r#"
-        SELECT 
-            COALESCE(total_trips, 0) as total_trips,
-            COALESCE(today_trips, 0) as today_trips,
-            COALESCE(weekend_trips, 0) as weekend_trips,
-            COALESCE(monthly_trips, 0) as monthly_trips,
-            CAST(COALESCE(avg_delivery_time, 0) AS DOUBLE) as "avg_delivery_time!",
-            CAST(COALESCE(avg_packing_time, 0) AS DOUBLE) as "avg_packing_time!",
-            CAST(COALESCE(avg_battery_consumption_per_km, 0) AS DOUBLE) as "avg_battery_consumption_per_km!",
-            CAST(COALESCE(cancellation_rate, 0) AS DOUBLE) as "cancellation_rate!",
-            COALESCE(active_accounts, 0) as active_accounts,
-            COALESCE(inactive_accounts, 0) as inactive_accounts,
-            COALESCE(total_accounts, 0) as total_accounts
-        FROM stats 
-        WHERE id = 1
+        SELECT
+            CAST(d.trips AS SIGNED) as "total_trips!: i32",
+            CAST(d.today_trips AS SIGNED) as "today_trips!: i32",
+            CAST(d.weekend_trips AS SIGNED) as "weekend_trips!: i32",
+            CAST(d.monthly_trips AS SIGNED) as "monthly_trips!: i32",
+            CAST(COALESCE(d.total_delivery_time / NULLIF(d.delivery_count, 0), 0)
+                AS DOUBLE) as "avg_delivery_time!",
+            CAST(COALESCE(d.total_packing_time / NULLIF(d.packing_count, 0), 0)
+                AS DOUBLE) as "avg_packing_time!",
+            CAST(COALESCE(d.battery_used / NULLIF(d.battery_distance, 0), 0)
+                AS DOUBLE) as "avg_battery_consumption_per_km!",
+            CAST(COALESCE(d.canceled_trips / NULLIF(d.trips, 0), 0)
+                AS DOUBLE) as "cancellation_rate!",
+            COALESCE(s.active_accounts, 0) as active_accounts,
+            COALESCE(s.inactive_accounts, 0) as inactive_accounts,
+            COALESCE(s.total_accounts, 0) as total_accounts
+        FROM stats s
+        CROSS JOIN (
+            SELECT
+                COALESCE(SUM(trips), 0) as trips,
+                COALESCE(SUM(CASE WHEN day = CURDATE() THEN trips END), 0) as today_trips,
+                COALESCE(SUM(CASE
+                    WHEN YEARWEEK(day, 1) = YEARWEEK(CURDATE(), 1) THEN trips
+                END), 0) as weekend_trips,
+                COALESCE(SUM(CASE
+                    WHEN EXTRACT(YEAR_MONTH FROM day) = EXTRACT(YEAR_MONTH FROM CURDATE())
+                    THEN trips
+                END), 0) as monthly_trips,
+                COALESCE(SUM(canceled_trips), 0) as canceled_trips,
+                SUM(total_delivery_time) as total_delivery_time,
+                SUM(delivery_count) as delivery_count,
+                SUM(total_packing_time) as total_packing_time,
+                SUM(packing_count) as packing_count,
+                SUM(battery_used) as battery_used,
+                SUM(battery_distance) as battery_distance
+            FROM daily_stats
+        ) d
+        WHERE s.id = 1
         "#
     )
     .fetch_one(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
+
+    // Without a range or granularity, only the global stats are returned
+    if query.from.is_none() && query.to.is_none() && query.granularity.is_none() {
+        return Ok(Json(StatsResponse {
+            stats,
+            series: None,
+        }));
+    }
+
+    // Default to the database's today, the buckets are keyed by its CURDATE()
+    let to = match query.to {
+        Some(to) => to,
+        None => {
+            sqlx::query_scalar!(r#"SELECT CURDATE() as "today!: chrono::NaiveDate""#)
+                .fetch_one(&state.db)
+                .await?
+        }
+    };
+    let from = query
+        .from
+        .unwrap_or(to - Duration::days(DEFAULT_SERIES_DAYS));
+    let granularity = query.granularity.unwrap_or_default();
+
+    // An explicit range was validated with the query, but `from` can still be after the default
+    if from > to {
+        return Err(ApiError::bad_request(
+            "invalid_range",
+            "from must not be after to",
+        ));
+    }
+
+    // Merge the daily buckets into the requested granularity
+    let series = sqlx::query_as!(
+        StatsBucket,
+        r#"
+        SELECT
+            CASE ?
+                WHEN 'week' THEN DATE_SUB(day, INTERVAL WEEKDAY(day) DAY)
+                WHEN 'month' THEN DATE_SUB(day, INTERVAL DAYOFMONTH(day) - 1 DAY)
+                ELSE day
+            END as "period_start!: chrono::NaiveDate",
+            CAST(SUM(trips) AS SIGNED) as "trips!: i64",
+            CAST(SUM(finished_trips) AS SIGNED) as "finished_trips!: i64",
+            CAST(SUM(canceled_trips) AS SIGNED) as "canceled_trips!: i64",
+            CAST(SUM(unfinished_trips) AS SIGNED) as "unfinished_trips!: i64",
+            CAST(COALESCE(SUM(total_delivery_time) / NULLIF(SUM(delivery_count), 0), 0)
+                AS DOUBLE) as "avg_delivery_time!",
+            CAST(COALESCE(SUM(total_packing_time) / NULLIF(SUM(packing_count), 0), 0)
+                AS DOUBLE) as "avg_packing_time!",
+            CAST(COALESCE(SUM(battery_used) / NULLIF(SUM(battery_distance), 0), 0)
+                AS DOUBLE) as "avg_battery_consumption_per_km!",
+            CAST(COALESCE(SUM(canceled_trips) / NULLIF(SUM(trips), 0), 0)
+                AS DOUBLE) as "cancellation_rate!",
+            CAST(SUM(new_accounts) AS SIGNED) as "new_accounts!: i64",
+            CAST(SUM(deactivated_accounts) AS SIGNED) as "deactivated_accounts!: i64"
+        FROM daily_stats
+        WHERE day BETWEEN ? AND ?
+        GROUP BY period_start
+        ORDER BY period_start ASC
+        "#,
+        granularity.as_str(),
+        from,
+        to
+    )
+    .fetch_all(&state.db)
+    .await?;
 
-    Ok(Json(stats))
+    Ok(Json(StatsResponse {
+        stats,
+        series: Some(series),
+    }))
 }
@@ -0,0 +1,83 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::trips::can_view_trip,
+    middleware::auth::Claims,
+    models::telemetry::TelemetryReading,
+    routes::users::login::AppState,
+};
+
+/// List the telemetry of a trip
+///
+/// Returns every reading recorded for the trip in chronological order, so the flight can be
+/// replayed.
+/// The drone owner, the customer who placed the order and the owner of the order's business
+/// can access it.
+#[utoipa::path(
+    get,
+    path = "/trips/{id}/telemetry",
+    tag = "Telemetry",
+    params(
+        ("id" = i32, Path, description = "Trip database id")
+    ),
+    responses(
+        (status = OK, description = "Telemetry retrieved successfully", body = Vec<TelemetryReading>),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not allowed to see this trip", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Trip not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn list_telemetry(
+    claims: Claims,
+    State(state): State<AppState>,
+    Path(trip_id): Path<i32>,
+) -> Result<Json<Vec<TelemetryReading>>, ApiError> {
+    // Extract user_id from JWT claims for security
+    let user_id: i32 = claims.sub.parse().map_err(|_| ApiError::Internal)?;
+
+    // Verify the trip exists
+    sqlx::query!("SELECT id FROM trips WHERE id = ?", trip_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("trip_not_found", "Trip not found"))?;
+
+    // Check if the requesting user can see the trip
+    let allowed = can_view_trip(&state.db, trip_id, user_id).await?;
+
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "trip_access_denied",
+            "User can't access this trip",
+        ));
+    }
+
+    // Query all readings of the trip
+    let readings = sqlx::query_as!(
+        TelemetryReading,
+        r#"
+        SELECT
+            id,
+            CAST(latitude AS DOUBLE) as "latitude!",
+            CAST(longitude AS DOUBLE) as "longitude!",
+            CAST(altitude AS DOUBLE) as "altitude!",
+            CAST(battery AS DOUBLE) as "battery!",
+            recorded_at
+        FROM trip_telemetry
+        WHERE trip_id = ?
+        ORDER BY recorded_at ASC, id ASC
+        "#,
+        trip_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(readings))
+}
@@ -0,0 +1,167 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    geo::{Coordinates, haversine_distance},
+    middleware::{
+        rbac::{AuthUser, Authorized, DroneOperator},
+        validation::ValidatedJson,
+    },
+    models::{
+        telemetry::{TelemetryRequest, TelemetryResponse},
+        trip::{TripEvent, TripState},
+    },
+    routes::users::login::AppState,
+};
+
+/// Record a telemetry reading for a trip
+///
+/// Drones call this endpoint periodically while flying, authenticated as their owner.
+/// Readings are only accepted for trips that are 'Requested' or 'Delivered'.
+/// Every reading newer than the previous one adds the distance between them to the trip's
+/// real_distance.
+#[utoipa::path(
+    post,
+    path = "/trips/{id}/telemetry",
+    tag = "Telemetry",
+    params(
+        ("id" = i32, Path, description = "Trip database id the reading belongs to")
+    ),
+    request_body = TelemetryRequest,
+    responses(
+        (status = OK, description = "Telemetry recorded successfully", body = TelemetryResponse),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner of the trip's drone", body = ProblemDetails),
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Trip not found", body = ProblemDetails),
+        (status = CONFLICT, description = "Trip is already finished, canceled or unfinished", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["drone_operator"])
+    )
+)]
+pub async fn record_telemetry(
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<DroneOperator>,
+    State(state): State<AppState>,
+    Path(trip_id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<TelemetryRequest>,
+) -> Result<Json<TelemetryResponse>, ApiError> {
+    // Start a transaction so concurrent readings accumulate the distance one at a time
+    let mut tx = state.db.begin().await?;
+
+    // Verify the trip exists, locking it until the transaction ends
+    let trip = sqlx::query!(
+        r#"
+        SELECT
+            t.id,
+            t.state,
+            CAST(t.real_distance AS DOUBLE) as real_distance,
+            d.user_id as drone_owner_id
+        FROM trips t
+        JOIN drones d ON t.drone_id = d.id
+        WHERE t.id = ?
+        FOR UPDATE
+        "#,
+        trip_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| ApiError::not_found("trip_not_found", "Trip not found"))?;
+
+    // Check if the requesting user is the owner of the drone
+    if trip.drone_owner_id != user_id {
+        return Err(ApiError::forbidden(
+            "not_drone_owner",
+            "User is not the owner of the drone",
+        ));
+    }
+
+    // Only trips in flight accept readings
+    let current: TripState = trip.state.parse().map_err(|_| ApiError::Internal)?;
+
+    if !matches!(current, TripState::Requested | TripState::Delivered) {
+        return Err(ApiError::conflict(
+            "trip_not_in_flight",
+            "Trip is not in flight",
+        ));
+    }
+
+    // Get the previous reading to measure the distance flown since then
+    let last_reading = sqlx::query!(
+        r#"
+        SELECT
+            CAST(latitude AS DOUBLE) as "latitude!",
+            CAST(longitude AS DOUBLE) as "longitude!",
+            recorded_at
+        FROM trip_telemetry
+        WHERE trip_id = ?
+        ORDER BY recorded_at DESC, id DESC
+        LIMIT 1
+        "#,
+        trip_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    // Readings that arrive out of order are stored but don't add distance
+    let segment = match (&last_reading, payload.timestamp) {
+        (Some(last), Some(timestamp)) if timestamp < last.recorded_at => 0.0,
+        (Some(last), _) => haversine_distance(
+            Coordinates::new(last.latitude, last.longitude),
+            Coordinates::new(payload.latitude, payload.longitude),
+        ),
+        (None, _) => 0.0,
+    };
+    let real_distance = trip.real_distance.unwrap_or(0.0) + segment;
+
+    // Insert the reading (recorded_at defaults to now when omitted)
+    let telemetry_result = sqlx::query!(
+        r#"
+        INSERT INTO trip_telemetry (trip_id, latitude, longitude, altitude, battery, recorded_at)
+        VALUES (?, ?, ?, ?, ?, COALESCE(?, NOW()))
+        "#,
+        trip_id,
+        payload.latitude,
+        payload.longitude,
+        payload.altitude,
+        payload.battery,
+        payload.timestamp
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let telemetry_id = telemetry_result.last_insert_id() as i32;
+
+    // Update the distance flown so far
+    sqlx::query!(
+        "UPDATE trips SET real_distance = ? WHERE id = ?",
+        real_distance,
+        trip_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Commit transaction
+    tx.commit().await?;
+
+    // Notify live subscribers (sending only fails when nobody is listening)
+    let _ = state.trip_events.send(TripEvent::Telemetry {
+        trip_id,
+        latitude: payload.latitude,
+        longitude: payload.longitude,
+        altitude: payload.altitude,
+        battery: payload.battery,
+        real_distance,
+    });
+
+    Ok(Json(TelemetryResponse {
+        telemetry_id,
+        trip_id,
+        real_distance,
+        message: "Telemetry recorded successfully".to_string(),
+    }))
+}
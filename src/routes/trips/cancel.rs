@@ -0,0 +1,116 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::stats::{add_trip_stats, remove_trip_stats},
+    middleware::rbac::{AuthUser, Authorized, Customer},
+    models::trip::{TripEvent, TripState, TripTransitionResponse},
+    routes::users::login::AppState,
+};
+
+/// Cancel a trip
+///
+/// Moves a trip from 'Requested' to 'Canceled' (User.cancel_trip()).
+/// The customer who placed the order or the owner of the drone can cancel it.
+/// canceled_time is stamped with the current time.
+/// The linked order stays 'Requested', so another drone can be dispatched for it.
+#[utoipa::path(
+    post,
+    path = "/trips/{id}/cancel",
+    tag = "Trips",
+    params(
+        ("id" = i32, Path, description = "Trip database id to cancel")
+    ),
+    responses(
+        (status = OK, description = "Trip canceled successfully", body = TripTransitionResponse),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User did not place the order and does not own the drone", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Trip not found", body = ProblemDetails),
+        (status = CONFLICT, description = "Trip is not in the 'Requested' state", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["customer"])
+    )
+)]
+pub async fn cancel_trip(
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<Customer>,
+    State(state): State<AppState>,
+    Path(trip_id): Path<i32>,
+) -> Result<Json<TripTransitionResponse>, ApiError> {
+    // Verify the trip exists and get the drone owner and the customer
+    let trip = sqlx::query!(
+        r#"
+        SELECT t.id, t.state, d.user_id as drone_owner_id, o.user_id as customer_id
+        FROM trips t
+        JOIN drones d ON t.drone_id = d.id
+        JOIN orders o ON t.order_id = o.id
+        WHERE t.id = ?
+        "#,
+        trip_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("trip_not_found", "Trip not found"))?;
+
+    // Check if the requesting user placed the order or owns the drone
+    if trip.customer_id != user_id && trip.drone_owner_id != user_id {
+        return Err(ApiError::forbidden(
+            "trip_access_denied",
+            "User did not place the order and does not own the drone",
+        ));
+    }
+
+    // Check that the state diagram allows this transition
+    let current: TripState = trip.state.parse().map_err(|_| ApiError::Internal)?;
+
+    if !current.can_transition_to(TripState::Canceled) {
+        return Err(ApiError::conflict(
+            "invalid_transition",
+            "Trip can't move to Canceled from its current state",
+        ));
+    }
+
+    // Start a transaction so the trip and the stats change together
+    let mut tx = state.db.begin().await?;
+
+    // Take the trip out of the stats, it is added back with its new values below
+    remove_trip_stats(&mut *tx, trip_id).await?;
+
+    // Update the trip, guarding on the current state so concurrent transitions can't both win
+    let result = sqlx::query!(
+        "UPDATE trips SET state = 'Canceled', canceled_time = NOW() WHERE id = ? AND state = \
+         'Requested'",
+        trip_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::conflict(
+            "invalid_transition",
+            "Trip can't move to Canceled from its current state",
+        ));
+    }
+
+    // Count the trip again with its new values
+    add_trip_stats(&mut *tx, trip_id).await?;
+
+    // Commit transaction
+    tx.commit().await?;
+
+    // Notify live subscribers (sending only fails when nobody is listening)
+    let _ = state.trip_events.send(TripEvent::StateChanged {
+        trip_id,
+        state: TripState::Canceled,
+    });
+
+    Ok(Json(TripTransitionResponse {
+        trip_id,
+        state: TripState::Canceled,
+        message: format!("Trip {} has been canceled", trip_id),
+    }))
+}
@@ -0,0 +1,146 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::stats::{add_trip_stats, remove_trip_stats},
+    middleware::{
+        rbac::{AuthUser, Authorized, DroneOperator},
+        validation::ValidatedJson,
+    },
+    models::trip::{DeliverTripRequest, TripEvent, TripState, TripTransitionResponse},
+    routes::users::login::AppState,
+};
+
+/// Mark a trip as delivered
+///
+/// Moves a trip from 'Requested' to 'Delivered' (Drone.start_return_trip()).
+/// Only the owner of the drone performing the trip can deliver it.
+/// arrival_time is stamped with the minutes elapsed since the trip was requested,
+/// and delivery_time with that same value minus the packing time.
+/// Battery readings that are not provided are taken from the trip's telemetry.
+#[utoipa::path(
+    post,
+    path = "/trips/{id}/deliver",
+    tag = "Trips",
+    params(
+        ("id" = i32, Path, description = "Trip database id to deliver")
+    ),
+    request_body = DeliverTripRequest,
+    responses(
+        (status = OK, description = "Trip delivered successfully", body = TripTransitionResponse),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner of the trip's drone", body = ProblemDetails),
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Trip not found", body = ProblemDetails),
+        (status = CONFLICT, description = "Trip is not in the 'Requested' state", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["drone_operator"])
+    )
+)]
+pub async fn deliver_trip(
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<DroneOperator>,
+    State(state): State<AppState>,
+    Path(trip_id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<DeliverTripRequest>,
+) -> Result<Json<TripTransitionResponse>, ApiError> {
+    // Verify the trip exists and get the owner of its drone
+    let trip = sqlx::query!(
+        r#"
+        SELECT t.id, t.state, d.user_id as drone_owner_id
+        FROM trips t
+        JOIN drones d ON t.drone_id = d.id
+        WHERE t.id = ?
+        "#,
+        trip_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("trip_not_found", "Trip not found"))?;
+
+    // Check if the requesting user is the owner of the drone
+    if trip.drone_owner_id != user_id {
+        return Err(ApiError::forbidden(
+            "not_drone_owner",
+            "User is not the owner of the drone",
+        ));
+    }
+
+    // Check that the state diagram allows this transition
+    let current: TripState = trip.state.parse().map_err(|_| ApiError::Internal)?;
+
+    if !current.can_transition_to(TripState::Delivered) {
+        return Err(ApiError::conflict(
+            "invalid_transition",
+            "Trip can't move to Delivered from its current state",
+        ));
+    }
+
+    // Start a transaction so the trip and the stats change together
+    let mut tx = state.db.begin().await?;
+
+    // Take the trip out of the stats, it is added back with its new values below
+    remove_trip_stats(&mut *tx, trip_id).await?;
+
+    // Update the trip, guarding on the current state so concurrent transitions can't both win
+    let result = sqlx::query!(
+        r#"
+        UPDATE trips SET
+            state = 'Delivered',
+            packing_time = ?,
+            battery_init = COALESCE(?, (
+                SELECT battery FROM trip_telemetry
+                WHERE trip_id = ?
+                ORDER BY recorded_at ASC, id ASC
+                LIMIT 1
+            )),
+            battery_arrival = COALESCE(?, (
+                SELECT battery FROM trip_telemetry
+                WHERE trip_id = ?
+                ORDER BY recorded_at DESC, id DESC
+                LIMIT 1
+            )),
+            arrival_time = TIMESTAMPDIFF(SECOND, request_time, NOW()) / 60,
+            delivery_time = TIMESTAMPDIFF(SECOND, request_time, NOW()) / 60 - COALESCE(?, 0)
+        WHERE id = ? AND state = 'Requested'
+        "#,
+        payload.packing_time,
+        payload.battery_init,
+        trip_id,
+        payload.battery_arrival,
+        trip_id,
+        payload.packing_time,
+        trip_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::conflict(
+            "invalid_transition",
+            "Trip can't move to Delivered from its current state",
+        ));
+    }
+
+    // Count the trip again with its new values
+    add_trip_stats(&mut *tx, trip_id).await?;
+
+    // Commit transaction
+    tx.commit().await?;
+
+    // Notify live subscribers (sending only fails when nobody is listening)
+    let _ = state.trip_events.send(TripEvent::StateChanged {
+        trip_id,
+        state: TripState::Delivered,
+    });
+
+    Ok(Json(TripTransitionResponse {
+        trip_id,
+        state: TripState::Delivered,
+        message: format!("Trip {} has been delivered", trip_id),
+    }))
+}
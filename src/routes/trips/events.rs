@@ -0,0 +1,73 @@
+use axum::{
+    extract::{Path, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use tokio_stream::{Stream, StreamExt, wrappers::BroadcastStream};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::trips::can_view_trip,
+    middleware::auth::Claims,
+    models::trip::TripEvent,
+    routes::users::login::AppState,
+};
+
+/// Stream live trip updates
+///
+/// Opens a Server-Sent Events stream with the trip's state changes (`state_changed`)
+/// and telemetry readings (`telemetry`) as they happen.
+/// The drone owner, the customer who placed the order and the owner of the order's business
+/// can subscribe.
+#[utoipa::path(
+    get,
+    path = "/trips/{id}/events",
+    tag = "Trips",
+    params(
+        ("id" = i32, Path, description = "Trip database id to follow")
+    ),
+    responses(
+        (status = OK, description = "Stream of trip events", content_type = "text/event-stream", body = TripEvent),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not allowed to see this trip", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Trip not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn stream_trip_events(
+    claims: Claims,
+    State(state): State<AppState>,
+    Path(trip_id): Path<i32>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    // Extract user_id from JWT claims for security
+    let user_id: i32 = claims.sub.parse().map_err(|_| ApiError::Internal)?;
+
+    // Verify the trip exists
+    sqlx::query!("SELECT id FROM trips WHERE id = ?", trip_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("trip_not_found", "Trip not found"))?;
+
+    // Check if the requesting user can see the trip
+    let allowed = can_view_trip(&state.db, trip_id, user_id).await?;
+
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "trip_access_denied",
+            "User can't access this trip",
+        ));
+    }
+
+    // Forward this trip's events, skipping any a slow subscriber missed
+    let stream = BroadcastStream::new(state.trip_events.subscribe()).filter_map(move |event| {
+        let event = event.ok()?;
+        if event.trip_id() != trip_id {
+            return None;
+        }
+        Some(Event::default().event(event.name()).json_data(&event))
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
@@ -0,0 +1,129 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::{
+        orders::sync_order_with_trip,
+        stats::{add_trip_stats, remove_trip_stats},
+    },
+    middleware::rbac::{AuthUser, Authorized, DroneOperator},
+    models::{
+        order::OrderState,
+        trip::{TripEvent, TripState, TripTransitionResponse},
+    },
+    routes::users::login::AppState,
+};
+
+/// Mark a trip as unfinished
+///
+/// Moves a trip from 'Requested' or 'Delivered' to 'Unfinished' (Drone.system_error()).
+/// Only the owner of the drone performing the trip can report the failure.
+/// unfinished_time is stamped with the current time.
+/// The linked order is moved to 'Uncomplete', or to 'Finished' when the trip failed on its way
+/// back, since the package was already delivered.
+#[utoipa::path(
+    post,
+    path = "/trips/{id}/fail",
+    tag = "Trips",
+    params(
+        ("id" = i32, Path, description = "Trip database id to mark as unfinished")
+    ),
+    responses(
+        (status = OK, description = "Trip marked as unfinished", body = TripTransitionResponse),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner of the trip's drone", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Trip not found", body = ProblemDetails),
+        (status = CONFLICT, description = "Trip is already finished, canceled or unfinished", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["drone_operator"])
+    )
+)]
+pub async fn fail_trip(
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<DroneOperator>,
+    State(state): State<AppState>,
+    Path(trip_id): Path<i32>,
+) -> Result<Json<TripTransitionResponse>, ApiError> {
+    // Verify the trip exists and get the owner of its drone
+    let trip = sqlx::query!(
+        r#"
+        SELECT t.id, t.state, d.user_id as drone_owner_id
+        FROM trips t
+        JOIN drones d ON t.drone_id = d.id
+        WHERE t.id = ?
+        "#,
+        trip_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("trip_not_found", "Trip not found"))?;
+
+    // Check if the requesting user is the owner of the drone
+    if trip.drone_owner_id != user_id {
+        return Err(ApiError::forbidden(
+            "not_drone_owner",
+            "User is not the owner of the drone",
+        ));
+    }
+
+    // Check that the state diagram allows this transition
+    let current: TripState = trip.state.parse().map_err(|_| ApiError::Internal)?;
+
+    if !current.can_transition_to(TripState::Unfinished) {
+        return Err(ApiError::conflict(
+            "invalid_transition",
+            "Trip can't move to Unfinished from its current state",
+        ));
+    }
+
+    // Start a transaction so the trip, its order and the stats change together
+    let mut tx = state.db.begin().await?;
+
+    // Take the trip out of the stats, it is added back with its new values below
+    remove_trip_stats(&mut *tx, trip_id).await?;
+
+    // Update the trip, guarding on the current state so concurrent transitions can't both win
+    let result = sqlx::query!(
+        "UPDATE trips SET state = 'Unfinished', unfinished_time = NOW() WHERE id = ? AND state = ?",
+        trip_id,
+        current.as_str()
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::conflict(
+            "invalid_transition",
+            "Trip can't move to Unfinished from its current state",
+        ));
+    }
+
+    // Count the trip again with its new values
+    add_trip_stats(&mut *tx, trip_id).await?;
+
+    // Keep the order consistent with the trip, a 'Delivered' trip already handed the package over
+    let order_state = match current {
+        TripState::Delivered => OrderState::Finished,
+        _ => OrderState::Uncomplete,
+    };
+    sync_order_with_trip(&mut *tx, trip_id, order_state).await?;
+
+    // Commit transaction
+    tx.commit().await?;
+
+    // Notify live subscribers (sending only fails when nobody is listening)
+    let _ = state.trip_events.send(TripEvent::StateChanged {
+        trip_id,
+        state: TripState::Unfinished,
+    });
+
+    Ok(Json(TripTransitionResponse {
+        trip_id,
+        state: TripState::Unfinished,
+        message: format!("Trip {} has been marked as unfinished", trip_id),
+    }))
+}
@@ -0,0 +1,148 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::{
+        orders::sync_order_with_trip,
+        stats::{add_trip_stats, remove_trip_stats},
+    },
+    middleware::{
+        rbac::{AuthUser, Authorized, DroneOperator},
+        validation::ValidatedJson,
+    },
+    models::{
+        order::OrderState,
+        trip::{FinishTripRequest, TripEvent, TripState, TripTransitionResponse},
+    },
+    routes::users::login::AppState,
+};
+
+/// Mark a trip as finished
+///
+/// Moves a trip from 'Delivered' to 'Finished' (Drone.finish_trip()).
+/// Only the owner of the drone performing the trip can finish it.
+/// total_time is stamped with the minutes elapsed since the trip was requested,
+/// and round_trip_time with that same value minus the packing time.
+/// battery_end and real_distance default to the values collected through telemetry.
+/// The linked order is moved to 'Finished'.
+#[utoipa::path(
+    post,
+    path = "/trips/{id}/finish",
+    tag = "Trips",
+    params(
+        ("id" = i32, Path, description = "Trip database id to finish")
+    ),
+    request_body = FinishTripRequest,
+    responses(
+        (status = OK, description = "Trip finished successfully", body = TripTransitionResponse),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner of the trip's drone", body = ProblemDetails),
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Trip not found", body = ProblemDetails),
+        (status = CONFLICT, description = "Trip is not in the 'Delivered' state", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["drone_operator"])
+    )
+)]
+pub async fn finish_trip(
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<DroneOperator>,
+    State(state): State<AppState>,
+    Path(trip_id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<FinishTripRequest>,
+) -> Result<Json<TripTransitionResponse>, ApiError> {
+    // Verify the trip exists and get the owner of its drone
+    let trip = sqlx::query!(
+        r#"
+        SELECT t.id, t.state, d.user_id as drone_owner_id
+        FROM trips t
+        JOIN drones d ON t.drone_id = d.id
+        WHERE t.id = ?
+        "#,
+        trip_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("trip_not_found", "Trip not found"))?;
+
+    // Check if the requesting user is the owner of the drone
+    if trip.drone_owner_id != user_id {
+        return Err(ApiError::forbidden(
+            "not_drone_owner",
+            "User is not the owner of the drone",
+        ));
+    }
+
+    // Check that the state diagram allows this transition
+    let current: TripState = trip.state.parse().map_err(|_| ApiError::Internal)?;
+
+    if !current.can_transition_to(TripState::Finished) {
+        return Err(ApiError::conflict(
+            "invalid_transition",
+            "Trip can't move to Finished from its current state",
+        ));
+    }
+
+    // Start a transaction so the trip, its order and the stats change together
+    let mut tx = state.db.begin().await?;
+
+    // Take the trip out of the stats, it is added back with its new values below
+    remove_trip_stats(&mut *tx, trip_id).await?;
+
+    // Update the trip, guarding on the current state so concurrent transitions can't both win
+    let result = sqlx::query!(
+        r#"
+        UPDATE trips SET
+            state = 'Finished',
+            battery_end = COALESCE(?, (
+                SELECT battery FROM trip_telemetry
+                WHERE trip_id = ?
+                ORDER BY recorded_at DESC, id DESC
+                LIMIT 1
+            )),
+            real_distance = COALESCE(?, real_distance),
+            total_time = TIMESTAMPDIFF(SECOND, request_time, NOW()) / 60,
+            round_trip_time = TIMESTAMPDIFF(SECOND, request_time, NOW()) / 60
+                - COALESCE(packing_time, 0)
+        WHERE id = ? AND state = 'Delivered'
+        "#,
+        payload.battery_end,
+        trip_id,
+        payload.real_distance,
+        trip_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::conflict(
+            "invalid_transition",
+            "Trip can't move to Finished from its current state",
+        ));
+    }
+
+    // Count the trip again with its new values
+    add_trip_stats(&mut *tx, trip_id).await?;
+
+    // Keep the order consistent with the trip
+    sync_order_with_trip(&mut *tx, trip_id, OrderState::Finished).await?;
+
+    // Commit transaction
+    tx.commit().await?;
+
+    // Notify live subscribers (sending only fails when nobody is listening)
+    let _ = state.trip_events.send(TripEvent::StateChanged {
+        trip_id,
+        state: TripState::Finished,
+    });
+
+    Ok(Json(TripTransitionResponse {
+        trip_id,
+        state: TripState::Finished,
+        message: format!("Trip {} has been finished", trip_id),
+    }))
+}
@@ -0,0 +1,61 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::trips::{can_view_trip, fetch_trip},
+    middleware::auth::Claims,
+    models::trip::Trip,
+    routes::users::login::AppState,
+};
+
+/// Get a trip
+///
+/// Returns the trip's state, distance, estimated time and both of its locations.
+/// The drone owner, the customer who placed the order and the owner of the order's business
+/// can access it.
+#[utoipa::path(
+    get,
+    path = "/trips/{id}",
+    tag = "Trips",
+    params(
+        ("id" = i32, Path, description = "Trip database id")
+    ),
+    responses(
+        (status = OK, description = "Trip retrieved successfully", body = Trip),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not allowed to see this trip", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Trip not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn get_trip(
+    claims: Claims,
+    State(state): State<AppState>,
+    Path(trip_id): Path<i32>,
+) -> Result<Json<Trip>, ApiError> {
+    // Extract user_id from JWT claims for security
+    let user_id: i32 = claims.sub.parse().map_err(|_| ApiError::Internal)?;
+
+    // Verify the trip exists
+    let trip = fetch_trip(&state.db, trip_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("trip_not_found", "Trip not found"))?;
+
+    // Check if the requesting user can see the trip
+    let allowed = can_view_trip(&state.db, trip_id, user_id).await?;
+
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "trip_access_denied",
+            "User can't access this trip",
+        ));
+    }
+
+    Ok(Json(trip))
+}
@@ -0,0 +1,62 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::trips::{can_view_trip, fetch_latest_trip_by_order},
+    middleware::auth::Claims,
+    models::trip::Trip,
+    routes::users::login::AppState,
+};
+
+/// Get the trip of an order
+///
+/// Returns the most recent trip registered for the order, with its state, distance,
+/// estimated time and both of its locations.
+/// The drone owner, the customer who placed the order and the owner of the order's business
+/// can access it.
+#[utoipa::path(
+    get,
+    path = "/orders/{id}/trip",
+    tag = "Trips",
+    params(
+        ("id" = i32, Path, description = "Order database id")
+    ),
+    responses(
+        (status = OK, description = "Trip retrieved successfully", body = Trip),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not allowed to see this trip", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Order not found or it has no trip yet", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn get_trip_by_order(
+    claims: Claims,
+    State(state): State<AppState>,
+    Path(order_id): Path<i32>,
+) -> Result<Json<Trip>, ApiError> {
+    // Extract user_id from JWT claims for security
+    let user_id: i32 = claims.sub.parse().map_err(|_| ApiError::Internal)?;
+
+    // Verify the order has a trip
+    let trip = fetch_latest_trip_by_order(&state.db, order_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("trip_not_found", "Trip not found"))?;
+
+    // Check if the requesting user can see the trip
+    let allowed = can_view_trip(&state.db, trip.id, user_id).await?;
+
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "trip_access_denied",
+            "User can't access this trip",
+        ));
+    }
+
+    Ok(Json(trip))
+}
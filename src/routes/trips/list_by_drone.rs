@@ -0,0 +1,63 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::trips::fetch_trips_by_drone,
+    middleware::auth::Claims,
+    models::trip::Trip,
+    routes::users::login::AppState,
+};
+
+/// List trips by drone
+///
+/// Returns every trip performed by a specific drone, newest first.
+/// Only the owner of the drone can access this endpoint.
+#[utoipa::path(
+    get,
+    path = "/drones/{id}/trips",
+    tag = "Trips",
+    params(
+        ("id" = i32, Path, description = "Drone ID to list trips from")
+    ),
+    responses(
+        (status = OK, description = "Trips retrieved successfully", body = Vec<Trip>),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner of this drone", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Drone not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn list_trips_by_drone(
+    claims: Claims,
+    State(state): State<AppState>,
+    Path(drone_id): Path<i32>,
+) -> Result<Json<Vec<Trip>>, ApiError> {
+    // Extract user_id from JWT claims for security
+    let user_id: i32 = claims.sub.parse().map_err(|_| ApiError::Internal)?;
+
+    // Verify that the drone exists and get its owner
+    let drone = state
+        .drones
+        .find(drone_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("drone_not_found", "Drone not found"))?;
+
+    // Check if the requesting user is the owner of the drone
+    if drone.user_id != user_id {
+        return Err(ApiError::forbidden(
+            "not_drone_owner",
+            "User is not the owner of the drone",
+        ));
+    }
+
+    // Query all trips performed by this drone
+    let trips = fetch_trips_by_drone(&state.db, drone_id).await?;
+
+    Ok(Json(trips))
+}
@@ -1 +1,9 @@
+pub mod cancel;
+pub mod deliver;
+pub mod events;
+pub mod fail;
+pub mod finish;
+pub mod get;
+pub mod get_by_order;
+pub mod list_by_drone;
 pub mod register;
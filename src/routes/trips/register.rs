@@ -1,17 +1,35 @@
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{Json, extract::State};
 
 use crate::{
-    middleware::auth::Claims,
-    models::trip::{RegisterTripRequest, RegisterTripResponse},
+    error::{ApiError, ProblemDetails},
+    geo::{Coordinates, estimate_flight_time, haversine_distance},
+    handlers::{stats::add_trip_stats, trips::has_trip_in_progress, zones::find_blocking_zone},
+    middleware::{
+        rbac::{AuthUser, Authorized, DroneOperator},
+        validation::ValidatedJson,
+    },
+    models::{
+        order::OrderState,
+        trip::{RegisterTripRequest, RegisterTripResponse},
+    },
     routes::users::login::AppState,
+    services::trips::plan_trip,
 };
 
 /// Register a new trip
 ///
 /// Creates a new trip for a drone delivery.
 /// The authenticated user must be the owner of the drone specified in the request.
+/// The order must be approved by its business and still be 'Requested', with no other trip in
+/// progress. The order is locked while the trip is created, so concurrent requests can't both
+/// register a trip for it.
 /// The trip is created with state 'Requested' by default.
 /// All nullable fields (packing_time, battery_init, etc.) are set to NULL initially.
+/// The distance (km) is the great-circle distance from the drone's position to the business,
+/// and est_time (minutes) is derived from that distance, the package weight and the drone's
+/// cruise speed.
+/// The trip is rejected if its straight-line route crosses a prohibited zone that is active
+/// during the flight.
 #[utoipa::path(
     post,
     path = "/trips/register",
@@ -19,92 +37,76 @@ use crate::{
     request_body = RegisterTripRequest,
     responses(
         (status = OK, description = "Trip registered successfully", body = RegisterTripResponse),
-        (status = UNAUTHORIZED, description = "Invalid or missing JWT token"),
-        (status = FORBIDDEN, description = "User is not the owner of the specified drone"),
-        (status = BAD_REQUEST, description = "Invalid request data"),
-        (status = NOT_FOUND, description = "Drone, order, or location not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not the owner of the specified drone", body = ProblemDetails),
+        (status = BAD_REQUEST, description = "Invalid request data or order not approved", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Drone, order, or location not found", body = ProblemDetails),
+        (status = CONFLICT, description = "Order already has a trip in progress", body = ProblemDetails),
+        (status = UNPROCESSABLE_ENTITY, description = "Route crosses an active prohibited zone", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
-        ("jwt" = [])
+        ("jwt" = ["drone_operator"])
     )
 )]
 pub async fn register_trip(
-    claims: Claims,
+    Authorized(AuthUser { id: user_id, .. }, _): Authorized<DroneOperator>,
     State(state): State<AppState>,
-    Json(payload): Json<RegisterTripRequest>,
-) -> Result<Json<RegisterTripResponse>, StatusCode> {
-    // Extract user_id from JWT claims
-    let user_id: i32 = claims
-        .sub
-        .parse()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    // Verify user exists and is active
-    let user = sqlx::query!("SELECT id, active FROM users WHERE id = ?", user_id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
-
-    if user.active == 0 {
-        return Err(StatusCode::FORBIDDEN);
-    }
-
-    // Validate input values
-    if payload.weight <= 0.0 {
-        return Err(StatusCode::BAD_REQUEST);
-    }
-
-    // Verify that the drone exists and belongs to the authenticated user
-    let drone = sqlx::query!(
-        "SELECT id, user_id, active FROM drones WHERE id = ?",
-        payload.drone_id
+    ValidatedJson(payload): ValidatedJson<RegisterTripRequest>,
+) -> Result<Json<RegisterTripResponse>, ApiError> {
+    // Check the user, drone and order against the business rules
+    let plan = plan_trip(
+        state.users.as_ref(),
+        state.drones.as_ref(),
+        state.orders.as_ref(),
+        user_id,
+        payload.drone_id,
+        payload.order_id,
     )
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
-
-    // Check if the user is the owner of the drone
-    if drone.user_id != user_id {
-        return Err(StatusCode::FORBIDDEN);
-    }
-
-    // Check if drone is active
-    if drone.active == 0 {
-        return Err(StatusCode::BAD_REQUEST);
+    .await?;
+    let to_location_id = plan.destination.id;
+
+    // Calculate the great-circle distance between the drone and the business
+    let from = Coordinates::new(payload.from_latitude, payload.from_longitude);
+    let to = Coordinates::new(plan.destination.latitude, plan.destination.longitude);
+    let distance = haversine_distance(from, to);
+
+    // Estimate the flight time from the distance, the payload and the drone's cruise speed
+    let est_time = estimate_flight_time(distance, payload.weight, plan.cruise_speed);
+
+    // Reject the trip if the route crosses a prohibited zone during the flight
+    if let Some(zone) = find_blocking_zone(&state.db, from, to, est_time).await? {
+        return Err(ApiError::RouteBlocked {
+            zone_id: zone.id,
+            description: zone.description,
+        });
     }
 
-    // Verify that the order exists
-    sqlx::query!("SELECT id FROM orders WHERE id = ?", payload.order_id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
+    // Start a transaction so the checks below, the from_location and the trip commit together
+    let mut tx = state.db.begin().await?;
 
-    // Get the business location from the order's products
-    // We assume all products in an order belong to the same business
-    let business_location = sqlx::query!(
-        r#"
-        SELECT b.location_id
-        FROM order_details od
-        JOIN products p ON od.product_id = p.id
-        JOIN businesses b ON p.business_id = b.id
-        WHERE od.order_id = ?
-        LIMIT 1
-        "#,
+    // Lock the order, so concurrent registrations for it run one after the other
+    let order = sqlx::query!(
+        "SELECT state, approved FROM orders WHERE id = ? FOR UPDATE",
         payload.order_id
     )
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::NOT_FOUND)?;
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Check the order again now that it is locked, it may have changed since it was planned
+    if order.approved == 0 || order.state != OrderState::Requested.as_str() {
+        return Err(ApiError::bad_request(
+            "order_not_ready",
+            "Order is not approved or no longer requested",
+        ));
+    }
 
-    // Verify that the business has a location assigned
-    let to_location_id = business_location
-        .location_id
-        .ok_or(StatusCode::BAD_REQUEST)?;
+    if has_trip_in_progress(&mut *tx, payload.order_id).await? {
+        return Err(ApiError::conflict(
+            "trip_in_progress",
+            "Order already has a trip in progress",
+        ));
+    }
 
     // Create from_location based on drone's current coordinates
     let from_location_result = sqlx::query!(
@@ -113,18 +115,11 @@ pub async fn register_trip(
         payload.from_latitude,
         payload.from_longitude
     )
-    .execute(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .execute(&mut *tx)
+    .await?;
 
     let from_location_id = from_location_result.last_insert_id() as i32;
 
-    // TODO: implement logic - Calculate distance based on from_location and to_location coordinates
-    let distance: f64 = 10.0;
-
-    // TODO: implement logic - Calculate estimated time based on distance and drone capabilities
-    let est_time: f64 = 10.0;
-
     // Insert the trip
     let trip_result = sqlx::query!(
         r#"
@@ -140,12 +135,17 @@ pub async fn register_trip(
         to_location_id,
         payload.drone_id
     )
-    .execute(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .execute(&mut *tx)
+    .await?;
 
     let trip_id = trip_result.last_insert_id() as i32;
 
+    // Count the trip in the stats
+    add_trip_stats(&mut *tx, trip_id).await?;
+
+    // Commit transaction, releasing the order
+    tx.commit().await?;
+
     Ok(Json(RegisterTripResponse {
         trip_id,
         weight: payload.weight,
@@ -0,0 +1,21 @@
+use axum::extract::Json;
+
+use crate::{middleware::keys::JWT_KEYS, models::jwk::Jwks};
+
+/// JWKS endpoint
+///
+/// - Public
+///
+/// Publishes the public keys that verify our tokens, so other services don't need a
+/// shared secret. Empty when tokens are signed with HS256.
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    tag = "Authentication",
+    responses(
+        (status = OK, description = "Public keys", body = Jwks)
+    )
+)]
+pub async fn get_jwks() -> Json<Jwks> {
+    Json(JWT_KEYS.jwks.clone())
+}
@@ -1,17 +1,51 @@
-use axum::{
-    extract::{Json, State},
-    http::StatusCode,
-};
+use std::sync::Arc;
+
+use axum::extract::{Json, State};
 use sqlx::mysql::MySqlPool;
+use tokio::sync::broadcast;
 
 use crate::{
-    middleware::auth::{create_token, verify_password},
-    models::user::{AuthResponse, LoginRequest},
+    error::{ApiError, ProblemDetails},
+    handlers::{
+        stats::update_last_login,
+        tokens::{issue_refresh_token, new_refresh_token_family},
+    },
+    middleware::{
+        auth::{create_token, verify_password},
+        validation::ValidatedJson,
+    },
+    models::{
+        trip::TripEvent,
+        user::{AuthResponse, LoginRequest},
+    },
+    repositories::{
+        drones::{DroneRepo, MySqlDroneRepo},
+        orders::{MySqlOrderRepo, OrderRepo},
+        users::{MySqlUserRepo, UserRepo},
+    },
 };
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: MySqlPool,
+    /// Live trip updates, consumed by the trip event streams
+    pub trip_events: broadcast::Sender<TripEvent>,
+    pub users: Arc<dyn UserRepo>,
+    pub drones: Arc<dyn DroneRepo>,
+    pub orders: Arc<dyn OrderRepo>,
+}
+
+impl AppState {
+    /// Build the state with the MySQL repositories on top of `db`
+    pub fn new(db: MySqlPool, trip_events: broadcast::Sender<TripEvent>) -> Self {
+        Self {
+            users: Arc::new(MySqlUserRepo::new(db.clone())),
+            drones: Arc::new(MySqlDroneRepo::new(db.clone())),
+            orders: Arc::new(MySqlOrderRepo::new(db.clone())),
+            db,
+            trip_events,
+        }
+    }
 }
 
 /// Login endpoint
@@ -24,37 +58,59 @@ pub struct AppState {
     request_body = LoginRequest,
     responses(
         (status = OK, description = "Login successful", body = AuthResponse),
-        (status = UNAUTHORIZED, description = "Invalid credentials"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = UNAUTHORIZED, description = "Invalid credentials", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not active", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     )
 )]
 pub async fn login(
     State(state): State<AppState>,
-    Json(payload): Json<LoginRequest>,
-) -> Result<Json<AuthResponse>, StatusCode> {
+    ValidatedJson(payload): ValidatedJson<LoginRequest>,
+) -> Result<Json<AuthResponse>, ApiError> {
     // Query user from database
     let user = sqlx::query!(
-        "SELECT id, username, password_hash FROM users WHERE username = ?",
+        "SELECT id, username, password_hash, active FROM users WHERE username = ?",
         payload.username
     )
     .fetch_optional(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .ok_or(StatusCode::UNAUTHORIZED)?;
+    .await?
+    .ok_or_else(|| ApiError::unauthorized("invalid_credentials", "Invalid username or password"))?;
 
     // Verify password
-    let password_hash = user.password_hash.ok_or(StatusCode::UNAUTHORIZED)?;
+    let password_hash = user.password_hash.ok_or_else(|| {
+        ApiError::unauthorized("invalid_credentials", "Invalid username or password")
+    })?;
     verify_password(&payload.password, &password_hash)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|_| ApiError::Internal)?
         .then_some(())
-        .ok_or(StatusCode::UNAUTHORIZED)?;
+        .ok_or_else(|| {
+            ApiError::unauthorized("invalid_credentials", "Invalid username or password")
+        })?;
+
+    // Deactivated users can't start a session
+    if user.active == 0 {
+        return Err(ApiError::forbidden(
+            "user_inactive",
+            "User account is inactive",
+        ));
+    }
+
+    // Update stats: record the login time
+    update_last_login(&state.db, user.id).await?;
 
     // Create JWT token
-    let token = create_token(user.id, user.username.clone())
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let token = create_token(user.id, user.username.clone()).map_err(|_| ApiError::Internal)?;
+
+    // Create the refresh token, starting a new family for this session
+    let mut conn = state.db.acquire().await?;
+
+    let refresh_token =
+        issue_refresh_token(&mut *conn, user.id, &new_refresh_token_family()).await?;
 
     Ok(Json(AuthResponse {
         token,
+        refresh_token,
         user_id: user.id,
         username: user.username,
     }))
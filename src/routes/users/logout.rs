@@ -0,0 +1,70 @@
+use axum::extract::{Json, State};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::tokens::{
+        RevokeReason, delete_expired_revoked_tokens, hash_refresh_token, revoke_access_token,
+        revoke_user_refresh_tokens,
+    },
+    middleware::{auth::Claims, validation::ValidatedJson},
+    models::user::{LogoutRequest, LogoutResponse},
+    routes::users::login::AppState,
+};
+
+/// Logout endpoint
+///
+/// Revokes the access token used to call it and the refresh token sent.
+/// Without a refresh token, every refresh token of the user is revoked (logout everywhere).
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    tag = "Authentication",
+    request_body = LogoutRequest,
+    responses(
+        (status = OK, description = "Logout successful", body = LogoutResponse),
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn logout(
+    claims: Claims,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<LogoutRequest>,
+) -> Result<Json<LogoutResponse>, ApiError> {
+    // Extract user_id from JWT claims
+    let user_id: i32 = claims.sub.parse().map_err(|_| ApiError::Internal)?;
+
+    // Deny the access token until it expires
+    revoke_access_token(&state.db, &claims.jti, claims.exp).await?;
+
+    // Logouts are the only way the denylist grows, so they also keep it small
+    delete_expired_revoked_tokens(&state.db).await?;
+
+    // Revoke the refresh tokens
+    let mut conn = state.db.acquire().await?;
+
+    match payload.refresh_token {
+        Some(refresh_token) => {
+            sqlx::query!(
+                "UPDATE refresh_tokens SET revoked_at = UTC_TIMESTAMP(), revoked_reason = ? WHERE \
+                 token_hash = ? AND user_id = ? AND revoked_at IS NULL",
+                RevokeReason::Logout.as_str(),
+                hash_refresh_token(&refresh_token),
+                user_id
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        None => {
+            revoke_user_refresh_tokens(&mut *conn, user_id, RevokeReason::Logout).await?;
+        }
+    }
+
+    Ok(Json(LogoutResponse {
+        message: "Logout successful".to_string(),
+    }))
+}
@@ -1,4 +1,8 @@
 pub mod delete;
+pub mod jwks;
 pub mod login;
+pub mod logout;
+pub mod refresh;
 pub mod register;
+pub mod stats;
 pub mod update;
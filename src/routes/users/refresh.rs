@@ -0,0 +1,109 @@
+use axum::extract::{Json, State};
+use chrono::Utc;
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    handlers::tokens::{
+        RevokeReason, hash_refresh_token, issue_refresh_token, revoke_refresh_token_family,
+    },
+    middleware::{auth::create_token, validation::ValidatedJson},
+    models::user::{AuthResponse, RefreshRequest},
+    routes::users::login::AppState,
+};
+
+/// Refresh endpoint
+///
+/// - Public
+///
+/// Exchanges a refresh token for a new access token and a new refresh token.
+/// Refresh tokens are single use: the one sent is revoked. Sending a token that was
+/// already rotated revokes the tokens rotated from the same login, since it may have been
+/// stolen. Tokens revoked by a logout are just rejected.
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    tag = "Authentication",
+    request_body = RefreshRequest,
+    responses(
+        (status = OK, description = "Tokens refreshed successfully", body = AuthResponse),
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = UNAUTHORIZED, description = "Invalid, expired or revoked refresh token", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    )
+)]
+pub async fn refresh(
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<RefreshRequest>,
+) -> Result<Json<AuthResponse>, ApiError> {
+    // Start a transaction
+    let mut tx = state.db.begin().await?;
+
+    // Lock the refresh token so it can only be rotated once
+    let stored = sqlx::query!(
+        r#"
+        SELECT
+            rt.id, rt.user_id, rt.family_id, rt.expires_at, rt.revoked_at, rt.revoked_reason,
+            u.username, u.active
+        FROM refresh_tokens rt
+        JOIN users u ON rt.user_id = u.id
+        WHERE rt.token_hash = ?
+        FOR UPDATE
+        "#,
+        hash_refresh_token(&payload.refresh_token)
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        ApiError::unauthorized(
+            "invalid_refresh_token",
+            "Refresh token is invalid, expired or revoked",
+        )
+    })?;
+
+    // A rotated token being used again means it leaked, revoke the rest of its family
+    if stored.revoked_at.is_some() {
+        if stored.revoked_reason.as_deref() == Some(RevokeReason::Rotated.as_str()) {
+            revoke_refresh_token_family(&mut *tx, &stored.family_id, RevokeReason::Reused).await?;
+
+            tx.commit().await?;
+        }
+
+        return Err(ApiError::unauthorized(
+            "invalid_refresh_token",
+            "Refresh token is invalid, expired or revoked",
+        ));
+    }
+
+    // Check the token is still valid and the user still active
+    if stored.expires_at <= Utc::now().naive_utc() || stored.active == 0 {
+        return Err(ApiError::unauthorized(
+            "invalid_refresh_token",
+            "Refresh token is invalid, expired or revoked",
+        ));
+    }
+
+    // Rotate: revoke the token sent and issue a new one
+    sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = UTC_TIMESTAMP(), revoked_reason = ? WHERE id = ?",
+        RevokeReason::Rotated.as_str(),
+        stored.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let refresh_token = issue_refresh_token(&mut *tx, stored.user_id, &stored.family_id).await?;
+
+    // Commit the transaction
+    tx.commit().await?;
+
+    // Create JWT token
+    let token =
+        create_token(stored.user_id, stored.username.clone()).map_err(|_| ApiError::Internal)?;
+
+    Ok(Json(AuthResponse {
+        token,
+        refresh_token,
+        user_id: stored.user_id,
+        username: stored.username,
+    }))
+}
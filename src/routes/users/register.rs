@@ -1,12 +1,10 @@
-use axum::{
-    extract::{Json, State},
-    http::StatusCode,
-};
+use axum::extract::{Json, State};
 
 use super::login::AppState;
 use crate::{
+    error::{ApiError, ProblemDetails},
     handlers::stats::increment_user_stats,
-    middleware::auth::hash_password,
+    middleware::{auth::hash_password, validation::ValidatedJson},
     models::user::{RegisterRequest, RegisterResponse},
 };
 
@@ -20,17 +18,17 @@ use crate::{
     request_body = RegisterRequest,
     responses(
         (status = OK, description = "Registration successful", body = RegisterResponse),
-        (status = CONFLICT, description = "Username already exists"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = CONFLICT, description = "Username already exists", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     )
 )]
 pub async fn register_handler(
     State(state): State<AppState>,
-    Json(payload): Json<RegisterRequest>,
-) -> Result<Json<RegisterResponse>, StatusCode> {
+    ValidatedJson(payload): ValidatedJson<RegisterRequest>,
+) -> Result<Json<RegisterResponse>, ApiError> {
     // Hash password
-    let password_hash =
-        hash_password(&payload.password).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let password_hash = hash_password(&payload.password).map_err(|_| ApiError::Internal)?;
 
     // Insert user into database
     sqlx::query!(
@@ -45,18 +43,20 @@ pub async fn register_handler(
     )
     .execute(&state.db)
     .await
-    .map_err(|_| StatusCode::CONFLICT)?; // Username already exists
+    .map_err(|err| match err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            ApiError::conflict("username_taken", "Username already exists")
+        }
+        err => err.into(),
+    })?;
 
     // Update stats: increment total_accounts and active_accounts
-    increment_user_stats(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    increment_user_stats(&state.db).await?;
 
     // Fetch the user by username to get the user_id safely
     let user = sqlx::query!("SELECT id FROM users WHERE username = ?", payload.username)
         .fetch_one(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     let user_id = user.id;
 
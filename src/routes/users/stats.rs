@@ -0,0 +1,67 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::rbac::AuthUser,
+    models::user::UserStats,
+    routes::users::login::AppState,
+};
+
+/// Get user stats endpoint
+///
+/// - Only the user themselves or an admin can see the stats
+/// - Users without trips or logins get zeroed counts
+#[utoipa::path(
+    get,
+    path = "/users/{id}/stats",
+    tag = "Users",
+    params(
+        ("id" = i32, Path, description = "User database id")
+    ),
+    responses(
+        (status = OK, description = "User stats", body = UserStats),
+        (status = FORBIDDEN, description = "User is not the requested user or an admin", body = ProblemDetails),
+        (status = NOT_FOUND, description = "User not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn get_user_stats(
+    user: AuthUser,
+    State(state): State<AppState>,
+    Path(user_id): Path<i32>,
+) -> Result<Json<UserStats>, ApiError> {
+    // Check if the requesting user is the user or an admin
+    if !user.is_self_or_admin(user_id) {
+        return Err(ApiError::forbidden(
+            "not_self_or_admin",
+            "Users can only see their own stats",
+        ));
+    }
+
+    // Fetch the stats, falling back to zeroes for users without a stats row
+    let stats = sqlx::query_as!(
+        UserStats,
+        r#"
+        SELECT
+            u.id as user_id,
+            CAST(COALESCE(us.trips, 0) AS SIGNED) as "trips!: i32",
+            CAST(COALESCE(us.canceled_trips, 0) AS SIGNED) as "canceled_trips!: i32",
+            us.last_login
+        FROM users u
+        LEFT JOIN user_stats us ON us.user_id = u.id
+        WHERE u.id = ?
+        "#,
+        user_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("user_not_found", "User not found"))?;
+
+    Ok(Json(stats))
+}
@@ -1,12 +1,12 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::StatusCode,
 };
 use serde::Serialize;
 
 use crate::{
-    middleware::auth::{Claims, hash_password},
+    error::{ApiError, ProblemDetails},
+    middleware::{auth::hash_password, rbac::AuthUser, validation::ValidatedJson},
     models::user::UpdateUserRequest,
     routes::users::login::AppState,
 };
@@ -30,51 +30,36 @@ pub struct UpdateUserResponse {
     request_body = UpdateUserRequest,
     responses(
         (status = OK, description = "User updated successfully", body = UpdateUserResponse),
-        (status = FORBIDDEN, description = "User is not authorized to update this user"),
-        (status = NOT_FOUND, description = "User not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not authorized to update this user", body = ProblemDetails),
+        (status = NOT_FOUND, description = "User not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
     ),
     security(
         ("jwt" = [])
     )
 )]
 pub async fn update_user(
-    claims: Claims,
+    user: AuthUser,
     State(state): State<AppState>,
     Path(user_id): Path<i32>,
-    Json(payload): Json<UpdateUserRequest>,
-) -> Result<Json<UpdateUserResponse>, StatusCode> {
-    // Get the requesting user's ID from the JWT claims
-    let requesting_user_id = claims
-        .sub
-        .parse::<i32>()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    // Check if the user is updating their own data
-    let is_own_user = requesting_user_id == user_id;
-
-    // Check if the requesting user is an admin
-    let is_admin = sqlx::query!(
-        "SELECT EXISTS(SELECT 1 FROM admins WHERE user_id = ?) as is_admin",
-        requesting_user_id
-    )
-    .fetch_one(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
+    ValidatedJson(payload): ValidatedJson<UpdateUserRequest>,
+) -> Result<Json<UpdateUserResponse>, ApiError> {
     // User must be either updating their own data or be an admin
-    if !is_own_user && is_admin.is_admin == 0 {
-        return Err(StatusCode::FORBIDDEN);
+    if !user.is_self_or_admin(user_id) {
+        return Err(ApiError::forbidden(
+            "not_self_or_admin",
+            "Users can only update their own data",
+        ));
     }
 
     // Verify the user exists
     let user_exists = sqlx::query!("SELECT id FROM users WHERE id = ?", user_id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     if user_exists.is_none() {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found("user_not_found", "User not found"));
     }
 
     // Build the update query dynamically based on provided fields
@@ -102,8 +87,7 @@ pub async fn update_user(
     }
 
     if let Some(password) = &payload.password {
-        let password_hash =
-            hash_password(password).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let password_hash = hash_password(password).map_err(|_| ApiError::Internal)?;
         updates.push("password_hash = ?");
         values.push(password_hash);
     }
@@ -125,10 +109,7 @@ pub async fn update_user(
     }
     query = query.bind(user_id);
 
-    query
-        .execute(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    query.execute(&state.db).await?;
 
     Ok(Json(UpdateUserResponse {
         message: format!("User {} has been updated successfully", user_id),
@@ -0,0 +1,58 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::rbac::{Admin, Authorized},
+    models::zone::DeleteZoneResponse,
+    routes::users::login::AppState,
+};
+
+/// Delete (expire) prohibited zone endpoint
+///
+/// - Admin only
+///
+/// The zone is soft-deleted by setting its `to` date to now, so it stops blocking
+/// trips but stays on record.
+#[utoipa::path(
+    delete,
+    path = "/zones/{id}",
+    tag = "Zones",
+    params(
+        ("id" = i32, Path, description = "Zone database id to delete")
+    ),
+    responses(
+        (status = OK, description = "Zone expired successfully", body = DeleteZoneResponse),
+        (status = FORBIDDEN, description = "User is not an admin", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Zone not found or already expired", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["admin"])
+    )
+)]
+pub async fn delete_zone(
+    _: Authorized<Admin>,
+    State(state): State<AppState>,
+    Path(zone_id): Path<i32>,
+) -> Result<Json<DeleteZoneResponse>, ApiError> {
+    // Expire the zone, unless it has already expired
+    let result = sqlx::query!(
+        "UPDATE prohibited_zones SET `to` = NOW() WHERE id = ? AND (`to` IS NULL OR `to` > NOW())",
+        zone_id
+    )
+    .execute(&state.db)
+    .await?;
+
+    // Check if any row was affected
+    if result.rows_affected() == 0 {
+        return Err(ApiError::not_found("zone_not_found", "Zone not found"));
+    }
+
+    Ok(Json(DeleteZoneResponse {
+        message: format!("Zone {} has been expired", zone_id),
+        zone_id,
+    }))
+}
@@ -0,0 +1,55 @@
+use axum::{Json, extract::State};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::auth::Claims,
+    models::zone::ProhibitedZone,
+    routes::users::login::AppState,
+};
+
+/// List prohibited zones
+///
+/// Returns all zones that are active now or will become active in the future,
+/// so drone operators can plan routes around them.
+/// Expired zones are not included in the response.
+#[utoipa::path(
+    get,
+    path = "/zones/list",
+    tag = "Zones",
+    responses(
+        (status = OK, description = "Zones retrieved successfully", body = Vec<ProhibitedZone>),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn list_zones(
+    _claims: Claims,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ProhibitedZone>>, ApiError> {
+    // Query all zones that haven't expired yet
+    let zones = sqlx::query_as!(
+        ProhibitedZone,
+        r#"
+        SELECT
+            z.id,
+            z.description,
+            z.since,
+            z.`to` as "to",
+            CAST(z.radius AS DOUBLE) as "radius!",
+            z.location_id,
+            CAST(l.latitude AS DOUBLE) as "latitude!",
+            CAST(l.longitude AS DOUBLE) as "longitude!"
+        FROM prohibited_zones z
+        JOIN locations l ON z.location_id = l.id
+        WHERE z.`to` IS NULL OR z.`to` > NOW()
+        ORDER BY z.since ASC
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(zones))
+}
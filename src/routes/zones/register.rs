@@ -0,0 +1,90 @@
+use axum::{Json, extract::State};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::{
+        rbac::{Admin, Authorized},
+        validation::ValidatedJson,
+    },
+    models::zone::{RegisterZoneRequest, RegisterZoneResponse},
+    routes::users::login::AppState,
+};
+
+/// Register a new prohibited zone
+///
+/// - Admin only
+///
+/// Creates a location for the zone's center and a no-fly zone around it.
+/// Trips whose route crosses the zone while it is active are rejected.
+#[utoipa::path(
+    post,
+    path = "/zones/register",
+    tag = "Zones",
+    request_body = RegisterZoneRequest,
+    responses(
+        (status = OK, description = "Zone registered successfully", body = RegisterZoneResponse),
+        (status = UNAUTHORIZED, description = "Invalid or missing JWT token", body = ProblemDetails),
+        (status = FORBIDDEN, description = "User is not an admin", body = ProblemDetails),
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["admin"])
+    )
+)]
+pub async fn register_zone(
+    _: Authorized<Admin>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<RegisterZoneRequest>,
+) -> Result<Json<RegisterZoneResponse>, ApiError> {
+    // Validate the active window
+    if let (Some(since), Some(to)) = (payload.since, payload.to)
+        && to <= since
+    {
+        return Err(ApiError::bad_request(
+            "invalid_window",
+            "to must be after since",
+        ));
+    }
+
+    // Start a transaction
+    let mut tx = state.db.begin().await?;
+
+    // Insert the zone's center location
+    let location_result = sqlx::query!(
+        "INSERT INTO locations (name, latitude, longitude) VALUES (?, ?, ?)",
+        payload.name,
+        payload.latitude,
+        payload.longitude
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let location_id = location_result.last_insert_id() as i32;
+
+    // Insert the zone (since defaults to now when omitted)
+    let zone_result = sqlx::query!(
+        r#"
+        INSERT INTO prohibited_zones (description, since, `to`, radius, location_id)
+        VALUES (?, COALESCE(?, NOW()), ?, ?, ?)
+        "#,
+        payload.description,
+        payload.since,
+        payload.to,
+        payload.radius,
+        location_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let zone_id = zone_result.last_insert_id() as i32;
+
+    // Commit transaction
+    tx.commit().await?;
+
+    Ok(Json(RegisterZoneResponse {
+        zone_id,
+        location_id,
+        message: "Zone registered successfully".to_string(),
+    }))
+}
@@ -0,0 +1,114 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    error::{ApiError, ProblemDetails},
+    middleware::{
+        rbac::{Admin, Authorized},
+        validation::ValidatedJson,
+    },
+    models::zone::{UpdateZoneRequest, UpdateZoneResponse},
+    routes::users::login::AppState,
+};
+
+/// Update prohibited zone endpoint
+///
+/// - Admin only
+#[utoipa::path(
+    put,
+    path = "/zones/{id}",
+    tag = "Zones",
+    params(
+        ("id" = i32, Path, description = "Zone database id to update")
+    ),
+    request_body = UpdateZoneRequest,
+    responses(
+        (status = OK, description = "Zone updated successfully", body = UpdateZoneResponse),
+        (status = FORBIDDEN, description = "User is not an admin", body = ProblemDetails),
+        (status = BAD_REQUEST, description = "Invalid request data", body = ProblemDetails),
+        (status = NOT_FOUND, description = "Zone not found", body = ProblemDetails),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error", body = ProblemDetails)
+    ),
+    security(
+        ("jwt" = ["admin"])
+    )
+)]
+pub async fn update_zone(
+    _: Authorized<Admin>,
+    State(state): State<AppState>,
+    Path(zone_id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<UpdateZoneRequest>,
+) -> Result<Json<UpdateZoneResponse>, ApiError> {
+    // Verify the zone exists and get its active window
+    let zone = sqlx::query!(
+        r#"SELECT id, since, `to` as "to" FROM prohibited_zones WHERE id = ?"#,
+        zone_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("zone_not_found", "Zone not found"))?;
+
+    // Validate the resulting active window
+    let since = payload.since.unwrap_or(zone.since);
+    if let Some(to) = payload.to.or(zone.to)
+        && to <= since
+    {
+        return Err(ApiError::bad_request(
+            "invalid_window",
+            "to must be after since",
+        ));
+    }
+
+    // Build the update query dynamically based on provided fields
+    let mut updates = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+
+    if let Some(description) = &payload.description {
+        updates.push("description = ?");
+        values.push(description.clone());
+    }
+
+    if let Some(since) = payload.since {
+        updates.push("since = ?");
+        values.push(since.to_string());
+    }
+
+    if let Some(to) = payload.to {
+        updates.push("`to` = ?");
+        values.push(to.to_string());
+    }
+
+    if let Some(radius) = payload.radius {
+        updates.push("radius = ?");
+        values.push(radius.to_string());
+    }
+
+    // If no fields to update, return early
+    if updates.is_empty() {
+        return Ok(Json(UpdateZoneResponse {
+            message: format!("No fields to update for zone {}", zone_id),
+            zone_id,
+        }));
+    }
+
+    // Execute the update query
+    let query_str = format!(
+        "UPDATE prohibited_zones SET {} WHERE id = ?",
+        updates.join(", ")
+    );
+
+    let mut query = sqlx::query(&query_str);
+    for value in &values {
+        query = query.bind(value);
+    }
+    query = query.bind(zone_id);
+
+    query.execute(&state.db).await?;
+
+    Ok(Json(UpdateZoneResponse {
+        message: format!("Zone {} has been updated successfully", zone_id),
+        zone_id,
+    }))
+}
@@ -0,0 +1,5 @@
+//! Business rules shared by the handlers, written against the `repositories` traits
+
+pub mod orders;
+pub mod trips;
+pub mod users;
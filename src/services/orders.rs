@@ -0,0 +1,169 @@
+use crate::{
+    error::ApiError,
+    models::order::OrderDetailRequest,
+    repositories::orders::{OrderLine, OrderRepo},
+};
+
+/// Products of a new order, priced when the order is placed
+#[derive(Debug)]
+pub struct PricedOrder {
+    /// Sum of (product price * amount) over every line
+    pub total_price: f64,
+    pub lines: Vec<OrderLine>,
+}
+
+/// Price the requested products, keeping a snapshot of their name and price
+///
+/// Only active products from verified businesses can be ordered, and all of them must come
+/// from the same business: it approves the whole order and the drone picks it up there.
+pub async fn price_order(
+    orders: &dyn OrderRepo,
+    details: &[OrderDetailRequest],
+) -> Result<PricedOrder, ApiError> {
+    let mut total_price: f64 = 0.0;
+    let mut lines = Vec::with_capacity(details.len());
+    let mut business_id = None;
+
+    for detail in details {
+        // Fetch product and verify it exists, is active, and belongs to a verified business
+        let product = orders
+            .find_product(detail.product_id)
+            .await?
+            .ok_or_else(|| ApiError::not_found("product_not_found", "Product not found"))?;
+
+        if !product.active {
+            return Err(ApiError::bad_request(
+                "product_inactive",
+                "Product is no longer available",
+            ));
+        }
+
+        if !product.business_verified {
+            return Err(ApiError::bad_request(
+                "business_not_verified",
+                "Product belongs to a business that is not verified",
+            ));
+        }
+
+        // Every product must come from the business of the first one
+        if *business_id.get_or_insert(product.business_id) != product.business_id {
+            return Err(ApiError::bad_request(
+                "mixed_businesses",
+                "All products of an order must belong to the same business",
+            ));
+        }
+
+        total_price += product.price * detail.amount as f64;
+
+        lines.push(OrderLine {
+            product_id: product.id,
+            product_name: product.name,
+            amount: detail.amount,
+            price: product.price,
+        });
+    }
+
+    Ok(PricedOrder { total_price, lines })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{fakes::FakeOrders, orders::OrderableProduct};
+
+    fn product(id: i32, price: f64) -> OrderableProduct {
+        OrderableProduct {
+            id,
+            name: format!("Product {}", id),
+            price,
+            active: true,
+            business_id: 1,
+            business_verified: true,
+        }
+    }
+
+    fn detail(product_id: i32, amount: i32) -> OrderDetailRequest {
+        OrderDetailRequest { product_id, amount }
+    }
+
+    #[tokio::test]
+    async fn sums_every_line() {
+        let orders = FakeOrders {
+            products: vec![product(1, 12.5), product(2, 3.0)],
+            ..Default::default()
+        };
+
+        let priced = price_order(&orders, &[detail(1, 2), detail(2, 5)])
+            .await
+            .unwrap();
+
+        assert_eq!(priced.total_price, 40.0);
+        assert_eq!(priced.lines.len(), 2);
+        assert_eq!(priced.lines[0].product_name, "Product 1");
+        assert_eq!(priced.lines[0].price, 12.5);
+        assert_eq!(priced.lines[1].amount, 5);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_products() {
+        let orders = FakeOrders::default();
+
+        let err = price_order(&orders, &[detail(1, 1)]).await.unwrap_err();
+        assert!(matches!(err, ApiError::NotFound("product_not_found", _)));
+    }
+
+    #[tokio::test]
+    async fn rejects_inactive_products() {
+        let orders = FakeOrders {
+            products: vec![OrderableProduct {
+                active: false,
+                ..product(1, 12.5)
+            }],
+            ..Default::default()
+        };
+
+        let err = price_order(&orders, &[detail(1, 1)]).await.unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest("product_inactive", _)));
+    }
+
+    #[tokio::test]
+    async fn rejects_products_of_unverified_businesses() {
+        let orders = FakeOrders {
+            products: vec![
+                product(1, 12.5),
+                OrderableProduct {
+                    business_verified: false,
+                    ..product(2, 3.0)
+                },
+            ],
+            ..Default::default()
+        };
+
+        let err = price_order(&orders, &[detail(1, 1), detail(2, 1)])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::BadRequest("business_not_verified", _)
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_products_of_several_businesses() {
+        let orders = FakeOrders {
+            products: vec![
+                product(1, 12.5),
+                OrderableProduct {
+                    business_id: 2,
+                    ..product(2, 3.0)
+                },
+            ],
+            ..Default::default()
+        };
+
+        let err = price_order(&orders, &[detail(1, 1), detail(2, 1)])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest("mixed_businesses", _)));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{
+    error::ApiError,
+    models::{location::Location, order::OrderState},
+    repositories::{drones::DroneRepo, orders::OrderRepo, users::UserRepo},
+    services::users::require_active_user,
+};
+
+/// What a new trip needs once its request passed the business rules
+#[derive(Debug)]
+pub struct TripPlan {
+    /// Cruise speed of the drone in km/h
+    pub cruise_speed: f64,
+    /// Location of the business selling the order
+    pub destination: Location,
+}
+
+/// Check that `user_id` can fly `drone_id` to deliver `order_id`
+///
+/// The user must be active and own the drone, the drone must be active, and the order must be
+/// approved, still 'Requested' and sold by a business that has a location. Whether the order
+/// already has a trip in progress is checked by `register_trip` once the order is locked.
+pub async fn plan_trip(
+    users: &dyn UserRepo,
+    drones: &dyn DroneRepo,
+    orders: &dyn OrderRepo,
+    user_id: i32,
+    drone_id: i32,
+    order_id: i32,
+) -> Result<TripPlan, ApiError> {
+    require_active_user(users, user_id).await?;
+
+    // Verify that the drone exists and belongs to the user
+    let drone = drones
+        .find(drone_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("drone_not_found", "Drone not found"))?;
+
+    if drone.user_id != user_id {
+        return Err(ApiError::forbidden(
+            "not_drone_owner",
+            "User is not the owner of the drone",
+        ));
+    }
+
+    if !drone.active {
+        return Err(ApiError::bad_request("drone_inactive", "Drone is inactive"));
+    }
+
+    // Verify that the order exists, is approved and is still 'Requested'
+    let order = orders
+        .find(order_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("order_not_found", "Order not found"))?;
+
+    if !order.approved || order.state != OrderState::Requested {
+        return Err(ApiError::bad_request(
+            "order_not_ready",
+            "Order is not approved or no longer requested",
+        ));
+    }
+
+    // The trip flies to the business, which needs a location
+    let destination = orders.business_location(order_id).await?.ok_or_else(|| {
+        ApiError::bad_request(
+            "business_without_location",
+            "Business has no location assigned",
+        )
+    })?;
+
+    Ok(TripPlan {
+        cruise_speed: drone.cruise_speed,
+        destination,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::repositories::{
+        drones::DroneRecord,
+        fakes::{FakeDrones, FakeOrders, FakeUsers},
+        orders::OrderRecord,
+        users::UserAccount,
+    };
+
+    const OPERATOR: i32 = 1;
+    const CUSTOMER: i32 = 2;
+    const DRONE: i32 = 10;
+    const ORDER: i32 = 20;
+
+    fn users() -> FakeUsers {
+        FakeUsers {
+            users: vec![
+                UserAccount {
+                    id: OPERATOR,
+                    active: true,
+                },
+                UserAccount {
+                    id: CUSTOMER,
+                    active: true,
+                },
+            ],
+        }
+    }
+
+    fn drone() -> DroneRecord {
+        DroneRecord {
+            id: DRONE,
+            user_id: OPERATOR,
+            number: 1,
+            active: true,
+            cruise_speed: 55.0,
+        }
+    }
+
+    fn drones(drone: DroneRecord) -> FakeDrones {
+        FakeDrones {
+            drones: Mutex::new(vec![drone]),
+        }
+    }
+
+    fn order() -> OrderRecord {
+        OrderRecord {
+            id: ORDER,
+            user_id: CUSTOMER,
+            state: OrderState::Requested,
+            approved: true,
+        }
+    }
+
+    fn store() -> Location {
+        Location {
+            id: 30,
+            name: Some("Storefront".to_string()),
+            latitude: -34.6037,
+            longitude: -58.3816,
+        }
+    }
+
+    fn orders(order: OrderRecord) -> FakeOrders {
+        FakeOrders {
+            orders: vec![order],
+            locations: vec![(ORDER, store())],
+            ..Default::default()
+        }
+    }
+
+    async fn plan(
+        drones: &FakeDrones,
+        orders: &FakeOrders,
+        user_id: i32,
+    ) -> Result<TripPlan, ApiError> {
+        plan_trip(&users(), drones, orders, user_id, DRONE, ORDER).await
+    }
+
+    #[tokio::test]
+    async fn plans_a_trip_to_the_business() {
+        let trip = plan(&drones(drone()), &orders(order()), OPERATOR)
+            .await
+            .unwrap();
+
+        assert_eq!(trip.cruise_speed, 55.0);
+        assert_eq!(trip.destination, store());
+    }
+
+    #[tokio::test]
+    async fn only_the_drone_owner_can_fly_it() {
+        let err = plan(&drones(drone()), &orders(order()), CUSTOMER)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::Forbidden("not_drone_owner", _)));
+    }
+
+    #[tokio::test]
+    async fn inactive_drones_cant_fly() {
+        let drone = DroneRecord {
+            active: false,
+            ..drone()
+        };
+
+        let err = plan(&drones(drone), &orders(order()), OPERATOR)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest("drone_inactive", _)));
+    }
+
+    #[tokio::test]
+    async fn orders_must_be_approved() {
+        let order = OrderRecord {
+            approved: false,
+            ..order()
+        };
+
+        let err = plan(&drones(drone()), &orders(order), OPERATOR)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest("order_not_ready", _)));
+    }
+
+    #[tokio::test]
+    async fn orders_must_still_be_requested() {
+        let order = OrderRecord {
+            state: OrderState::Canceled,
+            ..order()
+        };
+
+        let err = plan(&drones(drone()), &orders(order), OPERATOR)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest("order_not_ready", _)));
+    }
+
+    #[tokio::test]
+    async fn the_business_needs_a_location() {
+        let orders = FakeOrders {
+            locations: Vec::new(),
+            ..orders(order())
+        };
+
+        let err = plan(&drones(drone()), &orders, OPERATOR).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::BadRequest("business_without_location", _)
+        ));
+    }
+
+    #[tokio::test]
+    async fn inactive_users_cant_fly() {
+        let users = FakeUsers {
+            users: vec![UserAccount {
+                id: OPERATOR,
+                active: false,
+            }],
+        };
+
+        let err = plan_trip(
+            &users,
+            &drones(drone()),
+            &orders(order()),
+            OPERATOR,
+            DRONE,
+            ORDER,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ApiError::Forbidden("user_inactive", _)));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{
+    error::ApiError,
+    repositories::users::{UserAccount, UserRepo},
+};
+
+/// Fetch the user making a request, rejecting deactivated accounts
+pub async fn require_active_user(
+    users: &dyn UserRepo,
+    user_id: i32,
+) -> Result<UserAccount, ApiError> {
+    let user = users
+        .find(user_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("user_not_found", "User not found"))?;
+
+    if !user.active {
+        return Err(ApiError::forbidden(
+            "user_inactive",
+            "User account is inactive",
+        ));
+    }
+
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::fakes::FakeUsers;
+
+    fn users() -> FakeUsers {
+        FakeUsers {
+            users: vec![
+                UserAccount {
+                    id: 1,
+                    active: true,
+                },
+                UserAccount {
+                    id: 2,
+                    active: false,
+                },
+            ],
+        }
+    }
+
+    #[tokio::test]
+    async fn accepts_active_users() {
+        let user = require_active_user(&users(), 1).await.unwrap();
+        assert_eq!(user.id, 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_inactive_users() {
+        let err = require_active_user(&users(), 2).await.unwrap_err();
+        assert!(matches!(err, ApiError::Forbidden("user_inactive", _)));
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_users() {
+        let err = require_active_user(&users(), 3).await.unwrap_err();
+        assert!(matches!(err, ApiError::NotFound("user_not_found", _)));
+    }
+}
@@ -0,0 +1,311 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, assert_problem, id};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+#[sqlx::test]
+async fn register_and_list_businesses(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let owner = app.user("owner").await;
+    let other = app.user("other").await;
+
+    let (status, body) = app
+        .post(
+            "/business/register",
+            &owner.token,
+            json!({ "name": "Panadería Sur", "description": "Fresh bread" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "owner_id"), owner.id);
+    assert_eq!(body["verified"], false);
+    assert_eq!(body["verification_status"], "Pending");
+    let business_id = id(&body, "business_id");
+
+    let (status, body) = app.get("/business/list", &owner.token).await;
+    assert_eq!(status, StatusCode::OK);
+    let businesses = body.as_array().unwrap();
+    assert_eq!(businesses.len(), 1);
+    assert_eq!(id(&businesses[0], "id"), business_id);
+
+    // Other users only see their own businesses
+    let (status, body) = app.get("/business/list", &other.token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.as_array().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn review_requires_admin(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let admin = app.admin("admin").await;
+    let owner = app.user("owner").await;
+    let business_id = app.business(&owner).await;
+
+    assert_problem(
+        app.get("/business/pending", &owner.token).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+    assert_problem(
+        app.post_empty(&format!("/business/{}/verify", business_id), &owner.token)
+            .await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+    assert_problem(
+        app.post(
+            &format!("/business/{}/reject", business_id),
+            &owner.token,
+            json!({ "reason": "Self review" }),
+        )
+        .await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app.get("/business/pending", &admin.token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(id(&body[0], "id"), business_id);
+}
+
+#[sqlx::test]
+async fn verify_business(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let admin = app.admin("admin").await;
+    let owner = app.user("owner").await;
+    let business_id = app.business(&owner).await;
+
+    let uri = format!("/business/{}/verify", business_id);
+    let (status, body) = app.post_empty(&uri, &admin.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["verification_status"], "Verified");
+
+    // Only pending businesses can be reviewed
+    assert_problem(
+        app.post_empty(&uri, &admin.token).await,
+        StatusCode::CONFLICT,
+        "not_pending",
+    );
+
+    let (_, body) = app.get("/business/pending", &admin.token).await;
+    assert!(body.as_array().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn reject_business(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let admin = app.admin("admin").await;
+    let owner = app.user("owner").await;
+    let business_id = app.business(&owner).await;
+
+    let (status, body) = app
+        .post(
+            &format!("/business/{}/reject", business_id),
+            &admin.token,
+            json!({ "reason": "Missing tax documents" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["verification_status"], "Rejected");
+
+    // The owner can read the reason
+    let (_, body) = app.get("/business/list", &owner.token).await;
+    assert_eq!(body[0]["verification_status"], "Rejected");
+    assert_eq!(body[0]["rejection_reason"], "Missing tax documents");
+}
+
+#[sqlx::test]
+async fn update_and_delete_require_ownership(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let owner = app.user("owner").await;
+    let other = app.user("other").await;
+    let business_id = app.business(&owner).await;
+
+    let uri = format!("/business/{}", business_id);
+
+    assert_problem(
+        app.put(&uri, &other.token, json!({ "name": "Taken over" }))
+            .await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+    assert_problem(
+        app.delete(&uri, &other.token).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    // Owning a business doesn't give access to the others
+    let rival = app.user("rival").await;
+    app.business(&rival).await;
+    assert_problem(
+        app.put(&uri, &rival.token, json!({ "name": "Taken over" }))
+            .await,
+        StatusCode::FORBIDDEN,
+        "not_business_staff",
+    );
+    assert_problem(
+        app.delete(&uri, &rival.token).await,
+        StatusCode::FORBIDDEN,
+        "not_business_owner",
+    );
+
+    let (status, body) = app
+        .put(
+            &uri,
+            &owner.token,
+            json!({ "description": "Bread and pastries" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, body) = app.delete(&uri, &owner.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "business_id"), business_id);
+
+    // Deleted businesses are no longer listed
+    let (_, body) = app.get("/business/list", &owner.token).await;
+    assert!(body.as_array().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn set_location_requires_staff(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let owner = app.user("owner").await;
+    let other = app.user("other").await;
+    let business_id = app.business(&owner).await;
+
+    let uri = format!("/business/{}/location", business_id);
+    let location = json!({ "name": "Storefront", "latitude": -34.6, "longitude": -58.38 });
+
+    assert_problem(
+        app.post(&uri, &other.token, location.clone()).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app.post(&uri, &owner.token, location).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert!(id(&body, "location_id") > 0);
+
+    // Coordinates outside the valid range are rejected
+    let (status, body) = app
+        .post(
+            &uri,
+            &owner.token,
+            json!({ "latitude": 91.0, "longitude": 0.0 }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["field"], "latitude");
+}
+
+#[sqlx::test]
+async fn manage_employees(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let owner = app.user("owner").await;
+    let employee = app.user("employee").await;
+    let other = app.user("other").await;
+    let business_id = app.business(&owner).await;
+
+    let uri = format!("/business/{}/employees", business_id);
+
+    // Only the owner can hire
+    assert_problem(
+        app.post(&uri, &other.token, json!({ "username": "other" }))
+            .await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app
+        .post(&uri, &owner.token, json!({ "username": "employee" }))
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "user_id"), employee.id);
+
+    assert_problem(
+        app.post(&uri, &owner.token, json!({ "username": "employee" }))
+            .await,
+        StatusCode::CONFLICT,
+        "already_employee",
+    );
+    assert_problem(
+        app.post(&uri, &owner.token, json!({ "username": "owner" }))
+            .await,
+        StatusCode::BAD_REQUEST,
+        "owner_as_employee",
+    );
+
+    // Staff can list the employees, outsiders can't
+    let (status, body) = app.get(&uri, &employee.token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[0]["username"], "employee");
+    assert_problem(
+        app.get(&uri, &other.token).await,
+        StatusCode::FORBIDDEN,
+        "not_business_staff",
+    );
+
+    // Employees see the business in their list
+    let (_, body) = app.get("/business/list", &employee.token).await;
+    assert_eq!(id(&body[0], "id"), business_id);
+
+    // Only the owner can fire
+    let employee_uri = format!("{}/{}", uri, employee.id);
+    assert_problem(
+        app.delete(&employee_uri, &other.token).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+    let (status, body) = app.delete(&employee_uri, &owner.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (_, body) = app.get(&uri, &owner.token).await;
+    assert!(body.as_array().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn analytics_require_staff(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let other = app.user("other").await;
+
+    let uri = format!("/business/{}/analytics", market.business_id);
+
+    assert_problem(
+        app.get(&uri, &other.token).await,
+        StatusCode::FORBIDDEN,
+        "not_business_staff",
+    );
+
+    let (status, body) = app.get(&uri, &market.owner.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "business_id"), market.business_id);
+    assert_eq!(body["order_count"], 0);
+
+    // Admins can read any business' analytics
+    let (status, _) = app.get(&uri, &market.admin.token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_problem(
+        app.get(
+            &format!("{}?from=2025-02-01&to=2025-01-01", uri),
+            &market.owner.token,
+        )
+        .await,
+        StatusCode::BAD_REQUEST,
+        "invalid_range",
+    );
+
+    for top in [0, 51] {
+        assert_problem(
+            app.get(&format!("{}?top={}", uri, top), &market.owner.token)
+                .await,
+            StatusCode::BAD_REQUEST,
+            "validation_failed",
+        );
+    }
+}
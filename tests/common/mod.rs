@@ -0,0 +1,341 @@
+#![allow(dead_code)]
+
+use std::sync::Once;
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+    response::Response,
+};
+use backend::{app, routes::users::login::AppState};
+use http_body_util::BodyExt;
+use serde_json::{Value, json};
+use sqlx::MySqlPool;
+use tokio::sync::broadcast;
+use tower::ServiceExt;
+
+/// Coordinates of the test business
+pub const BUSINESS_LATITUDE: f64 = -34.6037;
+pub const BUSINESS_LONGITUDE: f64 = -58.3816;
+/// Coordinates the test drones take off from, about 3 km from the business
+pub const DRONE_LATITUDE: f64 = -34.6280;
+pub const DRONE_LONGITUDE: f64 = -58.3960;
+
+static LOAD_ENV: Once = Once::new();
+
+/// The real application router backed by an ephemeral database
+pub struct TestApp {
+    pub router: Router,
+    pub pool: MySqlPool,
+}
+
+/// A registered and logged in user
+pub struct TestUser {
+    pub id: i32,
+    pub username: String,
+    pub token: String,
+    pub refresh_token: String,
+}
+
+/// A verified business with a location and a product, an operator with a drone and a
+/// customer, ready to place orders and fly trips
+pub struct Marketplace {
+    pub admin: TestUser,
+    pub owner: TestUser,
+    pub customer: TestUser,
+    pub operator: TestUser,
+    pub business_id: i32,
+    pub product_id: i32,
+    pub drone_id: i32,
+}
+
+impl TestApp {
+    /// Build the router on top of a database created by `#[sqlx::test]`
+    pub fn new(pool: MySqlPool) -> Self {
+        // The JWT settings are read lazily, so they must be set before the first request
+        LOAD_ENV.call_once(|| {
+            dotenvy::from_filename(".env.test").expect("Failed to load .env.test");
+        });
+
+        let (trip_events, _) = broadcast::channel(1024);
+        let router = app(AppState::new(pool.clone(), trip_events));
+
+        Self { router, pool }
+    }
+
+    /// Send a request and return the raw response, for bodies that are streamed
+    pub async fn send(
+        &self,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Option<Value>,
+    ) -> Response {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let body = match body {
+            Some(body) => {
+                builder = builder.header(header::CONTENT_TYPE, "application/json");
+                Body::from(body.to_string())
+            }
+            None => Body::empty(),
+        };
+
+        self.router
+            .clone()
+            .oneshot(builder.body(body).unwrap())
+            .await
+            .unwrap()
+    }
+
+    /// Send a request and return the status with the JSON body (Null when empty)
+    pub async fn request(
+        &self,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let response = self.send(method, uri, token, body).await;
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).expect("Response body is not JSON")
+        };
+
+        (status, body)
+    }
+
+    pub async fn get(&self, uri: &str, token: &str) -> (StatusCode, Value) {
+        self.request(Method::GET, uri, Some(token), None).await
+    }
+
+    pub async fn post(&self, uri: &str, token: &str, body: Value) -> (StatusCode, Value) {
+        self.request(Method::POST, uri, Some(token), Some(body))
+            .await
+    }
+
+    /// POST to a transition endpoint that takes no body
+    pub async fn post_empty(&self, uri: &str, token: &str) -> (StatusCode, Value) {
+        self.request(Method::POST, uri, Some(token), None).await
+    }
+
+    pub async fn put(&self, uri: &str, token: &str, body: Value) -> (StatusCode, Value) {
+        self.request(Method::PUT, uri, Some(token), Some(body))
+            .await
+    }
+
+    pub async fn delete(&self, uri: &str, token: &str) -> (StatusCode, Value) {
+        self.request(Method::DELETE, uri, Some(token), None).await
+    }
+
+    /// Register a user and log them in
+    pub async fn user(&self, username: &str) -> TestUser {
+        let (status, body) = self
+            .request(
+                Method::POST,
+                "/auth/register",
+                None,
+                Some(json!({
+                    "username": username,
+                    "name": "Test",
+                    "lastname": "User",
+                    "phone": 1155550000,
+                    "email": format!("{}@example.com", username),
+                    "password": "correct-horse-battery",
+                })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "register {}: {}", username, body);
+
+        self.login(username).await
+    }
+
+    pub async fn login(&self, username: &str) -> TestUser {
+        let (status, body) = self
+            .request(
+                Method::POST,
+                "/auth/login",
+                None,
+                Some(json!({ "username": username, "password": "correct-horse-battery" })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "login {}: {}", username, body);
+
+        TestUser {
+            id: id(&body, "user_id"),
+            username: username.to_string(),
+            token: body["token"].as_str().unwrap().to_string(),
+            refresh_token: body["refresh_token"].as_str().unwrap().to_string(),
+        }
+    }
+
+    /// Register a user and grant them the admin role
+    pub async fn admin(&self, username: &str) -> TestUser {
+        let user = self.user(username).await;
+        sqlx::query("INSERT INTO admins (user_id) VALUES (?)")
+            .bind(user.id)
+            .execute(&self.pool)
+            .await
+            .unwrap();
+        user
+    }
+
+    /// Register a business, still pending review
+    pub async fn business(&self, owner: &TestUser) -> i32 {
+        let (status, body) = self
+            .post(
+                "/business/register",
+                &owner.token,
+                json!({ "name": "Panadería Sur", "description": "Fresh bread every morning" }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "register business: {}", body);
+        id(&body, "business_id")
+    }
+
+    /// Register a business, verify it and give it a location
+    pub async fn verified_business(&self, owner: &TestUser, admin: &TestUser) -> i32 {
+        let business_id = self.business(owner).await;
+
+        let (status, body) = self
+            .post_empty(&format!("/business/{}/verify", business_id), &admin.token)
+            .await;
+        assert_eq!(status, StatusCode::OK, "verify business: {}", body);
+
+        let (status, body) = self
+            .post(
+                &format!("/business/{}/location", business_id),
+                &owner.token,
+                json!({
+                    "name": "Storefront",
+                    "latitude": BUSINESS_LATITUDE,
+                    "longitude": BUSINESS_LONGITUDE,
+                }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "set location: {}", body);
+
+        business_id
+    }
+
+    pub async fn product(&self, staff: &TestUser, business_id: i32) -> i32 {
+        let (status, body) = self
+            .post(
+                "/product/register",
+                &staff.token,
+                json!({
+                    "name": "Medialunas",
+                    "description": "A dozen croissants",
+                    "price": 12.5,
+                    "business_id": business_id,
+                }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "register product: {}", body);
+        id(&body, "product_id")
+    }
+
+    pub async fn drone(&self, operator: &TestUser, number: i32) -> i32 {
+        let (status, body) = self
+            .post(
+                "/drones/register",
+                &operator.token,
+                json!({ "name": "Hornero", "number": number }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "register drone: {}", body);
+        id(&body, "drone_id")
+    }
+
+    /// Admin, business owner, customer and drone operator with everything a trip needs
+    pub async fn marketplace(&self) -> Marketplace {
+        let admin = self.admin("admin").await;
+        let owner = self.user("owner").await;
+        let customer = self.user("customer").await;
+        let operator = self.user("operator").await;
+
+        let business_id = self.verified_business(&owner, &admin).await;
+        let product_id = self.product(&owner, business_id).await;
+        let drone_id = self.drone(&operator, 1).await;
+
+        Marketplace {
+            admin,
+            owner,
+            customer,
+            operator,
+            business_id,
+            product_id,
+            drone_id,
+        }
+    }
+
+    /// Place an order for two units of the marketplace product
+    pub async fn order(&self, market: &Marketplace) -> i32 {
+        let (status, body) = self
+            .post(
+                "/orders/register",
+                &market.customer.token,
+                json!({ "order_details": [{ "product_id": market.product_id, "amount": 2 }] }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "register order: {}", body);
+        id(&body, "order_id")
+    }
+
+    /// Place an order and have the business owner approve it
+    pub async fn approved_order(&self, market: &Marketplace) -> i32 {
+        let order_id = self.order(market).await;
+        let (status, body) = self
+            .post_empty(
+                &format!("/orders/{}/approve", order_id),
+                &market.owner.token,
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "approve order: {}", body);
+        order_id
+    }
+
+    /// Register a trip for an approved order with the marketplace drone
+    pub async fn trip(&self, market: &Marketplace, order_id: i32) -> i32 {
+        let (status, body) = self
+            .post(
+                "/trips/register",
+                &market.operator.token,
+                trip_request(market, order_id),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "register trip: {}", body);
+        id(&body, "trip_id")
+    }
+}
+
+/// Body of a trip registration from the default take-off point
+pub fn trip_request(market: &Marketplace, order_id: i32) -> Value {
+    json!({
+        "weight": 1.5,
+        "order_id": order_id,
+        "from_latitude": DRONE_LATITUDE,
+        "from_longitude": DRONE_LONGITUDE,
+        "drone_id": market.drone_id,
+    })
+}
+
+/// Read an integer ID field from a response body
+pub fn id(body: &Value, field: &str) -> i32 {
+    body[field]
+        .as_i64()
+        .unwrap_or_else(|| panic!("missing {} in {}", field, body)) as i32
+}
+
+/// Assert a problem details response with the given status and error code
+pub fn assert_problem(response: (StatusCode, Value), status: StatusCode, code: &str) {
+    let (actual, body) = response;
+    assert_eq!(actual, status, "unexpected status: {}", body);
+    assert_eq!(body["code"], code, "unexpected error: {}", body);
+}
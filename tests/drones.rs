@@ -0,0 +1,119 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, assert_problem, id};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+#[sqlx::test]
+async fn register_and_list_drones(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let operator = app.user("operator").await;
+    let other = app.user("other").await;
+
+    let (status, body) = app
+        .post(
+            "/drones/register",
+            &operator.token,
+            json!({ "name": "Hornero", "number": 7, "cruise_speed": 55.0 }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let drone_id = id(&body, "drone_id");
+
+    let (status, body) = app.get("/drones/list", &operator.token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(id(&body[0], "id"), drone_id);
+    assert_eq!(body[0]["cruise_speed"], 55.0);
+
+    // Drones are only listed to their operator
+    let (_, body) = app.get("/drones/list", &other.token).await;
+    assert!(body.as_array().unwrap().is_empty());
+
+    // Drone numbers are unique
+    assert_problem(
+        app.post(
+            "/drones/register",
+            &other.token,
+            json!({ "name": "Benteveo", "number": 7 }),
+        )
+        .await,
+        StatusCode::CONFLICT,
+        "drone_number_taken",
+    );
+}
+
+#[sqlx::test]
+async fn delete_drone_requires_ownership(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let operator = app.user("operator").await;
+    let other = app.user("other").await;
+    let drone_id = app.drone(&operator, 1).await;
+
+    let uri = format!("/drones/{}", drone_id);
+
+    assert_problem(
+        app.delete(&uri, &other.token).await,
+        StatusCode::FORBIDDEN,
+        "not_drone_owner",
+    );
+
+    let (status, body) = app.delete(&uri, &operator.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "drone_id"), drone_id);
+
+    let (_, body) = app.get("/drones/list", &operator.token).await;
+    assert!(body.as_array().unwrap().is_empty());
+    assert_problem(
+        app.delete(&uri, &operator.token).await,
+        StatusCode::NOT_FOUND,
+        "drone_not_found",
+    );
+}
+
+#[sqlx::test]
+async fn metrics_require_ownership(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let admin = app.admin("admin").await;
+    let operator = app.user("operator").await;
+    let other = app.user("other").await;
+    let drone_id = app.drone(&operator, 1).await;
+
+    let uri = format!("/drones/{}/metrics", drone_id);
+
+    assert_problem(
+        app.get(&uri, &other.token).await,
+        StatusCode::FORBIDDEN,
+        "not_drone_owner",
+    );
+
+    let (status, body) = app.get(&uri, &operator.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "drone_id"), drone_id);
+    assert_eq!(body["total_flights"], 0);
+    assert!(body["last_flight"].is_null());
+
+    // Admins can read any drone's metrics
+    let (status, _) = app.get(&uri, &admin.token).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn drone_trips_require_ownership(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+
+    let uri = format!("/drones/{}/trips", market.drone_id);
+
+    assert_problem(
+        app.get(&uri, &market.customer.token).await,
+        StatusCode::FORBIDDEN,
+        "not_drone_owner",
+    );
+
+    let (status, body) = app.get(&uri, &market.operator.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body[0], "id"), trip_id);
+}
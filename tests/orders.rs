@@ -0,0 +1,243 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, assert_problem, id};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+#[sqlx::test]
+async fn register_order(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+
+    let (status, body) = app
+        .post(
+            "/orders/register",
+            &market.customer.token,
+            json!({ "order_details": [{ "product_id": market.product_id, "amount": 3 }] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["total_price"], 37.5);
+    assert_eq!(body["approved"], false);
+    assert_eq!(body["flight_number"].as_str().unwrap().len(), 5);
+    let order_id = id(&body, "order_id");
+
+    // The customer sees the order with a snapshot of the product
+    let (status, body) = app
+        .get(&format!("/orders/{}", order_id), &market.customer.token)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Requested");
+    assert_eq!(body["order_details"][0]["product_name"], "Medialunas");
+    assert_eq!(body["order_details"][0]["amount"], 3);
+
+    let (status, body) = app.get("/orders", &market.customer.token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(id(&body[0], "id"), order_id);
+}
+
+#[sqlx::test]
+async fn flight_numbers_are_unique(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+
+    let first = app.order(&market).await;
+    let second = app.order(&market).await;
+
+    let (_, first) = app
+        .get(&format!("/orders/{}", first), &market.customer.token)
+        .await;
+    let (_, second) = app
+        .get(&format!("/orders/{}", second), &market.customer.token)
+        .await;
+    assert_ne!(first["flight_number"], second["flight_number"]);
+}
+
+#[sqlx::test]
+async fn register_order_requires_a_verified_business(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let owner = app.user("owner").await;
+    let customer = app.user("customer").await;
+    let business_id = app.business(&owner).await;
+    let product_id = app.product(&owner, business_id).await;
+
+    assert_problem(
+        app.post(
+            "/orders/register",
+            &customer.token,
+            json!({ "order_details": [{ "product_id": product_id, "amount": 1 }] }),
+        )
+        .await,
+        StatusCode::BAD_REQUEST,
+        "business_not_verified",
+    );
+}
+
+#[sqlx::test]
+async fn register_order_validates_amounts(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+
+    let (status, body) = app
+        .post(
+            "/orders/register",
+            &market.customer.token,
+            json!({ "order_details": [{ "product_id": market.product_id, "amount": 0 }] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    assert_eq!(body["errors"][0]["field"], "order_details[0].amount");
+}
+
+#[sqlx::test]
+async fn get_order_requires_customer_or_staff(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.order(&market).await;
+
+    let uri = format!("/orders/{}", order_id);
+
+    assert_problem(
+        app.get(&uri, &market.operator.token).await,
+        StatusCode::FORBIDDEN,
+        "order_access_denied",
+    );
+
+    let (status, _) = app.get(&uri, &market.owner.token).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn business_orders_require_staff(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.order(&market).await;
+
+    let uri = format!("/business/{}/orders", market.business_id);
+
+    assert_problem(
+        app.get(&uri, &market.customer.token).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app.get(&uri, &market.owner.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body[0], "id"), order_id);
+
+    // Filtering by state
+    let (_, body) = app
+        .get(&format!("{}?state=Canceled", uri), &market.owner.token)
+        .await;
+    assert!(body.as_array().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn approve_order_requires_business_owner(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.order(&market).await;
+
+    let uri = format!("/orders/{}/approve", order_id);
+
+    assert_problem(
+        app.post_empty(&uri, &market.customer.token).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app.post_empty(&uri, &market.owner.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["approved"], true);
+    assert_eq!(body["state"], "Requested");
+
+    assert_problem(
+        app.post_empty(&uri, &market.owner.token).await,
+        StatusCode::CONFLICT,
+        "invalid_transition",
+    );
+}
+
+#[sqlx::test]
+async fn reject_order_requires_business_owner(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.order(&market).await;
+
+    let uri = format!("/orders/{}/reject", order_id);
+
+    assert_problem(
+        app.post_empty(&uri, &market.customer.token).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app.post_empty(&uri, &market.owner.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Canceled");
+
+    // A rejected order can't be approved anymore
+    assert_problem(
+        app.post_empty(
+            &format!("/orders/{}/approve", order_id),
+            &market.owner.token,
+        )
+        .await,
+        StatusCode::CONFLICT,
+        "invalid_transition",
+    );
+}
+
+#[sqlx::test]
+async fn cancel_order_requires_customer(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+
+    let uri = format!("/orders/{}/cancel", order_id);
+
+    assert_problem(
+        app.post_empty(&uri, &market.owner.token).await,
+        StatusCode::FORBIDDEN,
+        "not_order_customer",
+    );
+
+    let (status, body) = app.post_empty(&uri, &market.customer.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Canceled");
+
+    // The pending trip is canceled with the order
+    let (_, body) = app
+        .get(&format!("/trips/{}", trip_id), &market.customer.token)
+        .await;
+    assert_eq!(body["state"], "Canceled");
+}
+
+#[sqlx::test]
+async fn cancel_order_after_delivery_conflicts(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+
+    let (status, _) = app
+        .post(
+            &format!("/trips/{}/deliver", trip_id),
+            &market.operator.token,
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_problem(
+        app.post_empty(
+            &format!("/orders/{}/cancel", order_id),
+            &market.customer.token,
+        )
+        .await,
+        StatusCode::CONFLICT,
+        "order_delivered",
+    );
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, assert_problem, id};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+#[sqlx::test]
+async fn register_product_requires_staff(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let owner = app.user("owner").await;
+    let other = app.user("other").await;
+    let business_id = app.business(&owner).await;
+
+    let product = json!({
+        "name": "Medialunas",
+        "description": "A dozen croissants",
+        "price": 12.5,
+        "business_id": business_id,
+    });
+
+    assert_problem(
+        app.post("/product/register", &other.token, product.clone())
+            .await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app.post("/product/register", &owner.token, product).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let product_id = id(&body, "product_id");
+
+    let uri = format!("/product/business/{}", business_id);
+    let (status, body) = app.get(&uri, &owner.token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(id(&body[0], "id"), product_id);
+
+    assert_problem(
+        app.get(&uri, &other.token).await,
+        StatusCode::FORBIDDEN,
+        "not_business_staff",
+    );
+}
+
+#[sqlx::test]
+async fn register_product_rejects_a_non_positive_price(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let owner = app.user("owner").await;
+    let business_id = app.business(&owner).await;
+
+    let (status, body) = app
+        .post(
+            "/product/register",
+            &owner.token,
+            json!({
+                "name": "Free lunch",
+                "description": "There is no such thing",
+                "price": 0.0,
+                "business_id": business_id,
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    assert_eq!(body["errors"][0]["field"], "price");
+}
+
+#[sqlx::test]
+async fn employees_manage_products(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let owner = app.user("owner").await;
+    let employee = app.user("employee").await;
+    let business_id = app.business(&owner).await;
+
+    let (status, _) = app
+        .post(
+            &format!("/business/{}/employees", business_id),
+            &owner.token,
+            json!({ "username": "employee" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let product_id = app.product(&employee, business_id).await;
+
+    let (status, body) = app
+        .put(
+            &format!("/product/{}", product_id),
+            &employee.token,
+            json!({ "price": 14.0 }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "product_id"), product_id);
+}
+
+#[sqlx::test]
+async fn update_and_delete_require_staff(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let owner = app.user("owner").await;
+    let other = app.user("other").await;
+    let business_id = app.business(&owner).await;
+    let product_id = app.product(&owner, business_id).await;
+
+    let uri = format!("/product/{}", product_id);
+
+    assert_problem(
+        app.put(&uri, &other.token, json!({ "price": 1.0 })).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+    assert_problem(
+        app.delete(&uri, &other.token).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app
+        .put(
+            &uri,
+            &owner.token,
+            json!({ "name": "Medialunas de manteca" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, body) = app.delete(&uri, &owner.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "product_id"), product_id);
+
+    // Deleted products are no longer listed and can't be deleted twice
+    let (_, body) = app
+        .get(&format!("/product/business/{}", business_id), &owner.token)
+        .await;
+    assert!(body.as_array().unwrap().is_empty());
+    assert_problem(
+        app.delete(&uri, &owner.token).await,
+        StatusCode::NOT_FOUND,
+        "product_not_found",
+    );
+}
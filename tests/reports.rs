@@ -0,0 +1,179 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, assert_problem, id};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+#[sqlx::test]
+async fn register_and_list_reports(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+
+    let (status, body) = app
+        .post(
+            "/reports",
+            &market.customer.token,
+            json!({
+                "title": "Stale croissants",
+                "description": "They were delivered a day old",
+                "target": "Product",
+                "target_id": market.product_id,
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Open");
+    let report_id = id(&body, "report_id");
+
+    // Only admins review the reports
+    assert_problem(
+        app.get("/reports", &market.customer.token).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app
+        .get("/reports?state=Open&target=Product", &market.admin.token)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body[0], "id"), report_id);
+    assert_eq!(id(&body[0], "reporter_id"), market.customer.id);
+    assert_eq!(id(&body[0], "target_id"), market.product_id);
+
+    let (_, body) = app.get("/reports?target=User", &market.admin.token).await;
+    assert!(body.as_array().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn register_report_requires_an_existing_target(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let user = app.user("alice").await;
+
+    assert_problem(
+        app.post(
+            "/reports",
+            &user.token,
+            json!({
+                "title": "Ghost",
+                "description": "This trip doesn't exist",
+                "target": "Trip",
+                "target_id": 999,
+            }),
+        )
+        .await,
+        StatusCode::NOT_FOUND,
+        "target_not_found",
+    );
+}
+
+#[sqlx::test]
+async fn resolve_report_with_an_action(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+
+    let (_, body) = app
+        .post(
+            "/reports",
+            &market.customer.token,
+            json!({
+                "title": "Counterfeit",
+                "description": "Not the advertised product",
+                "target": "Product",
+                "target_id": market.product_id,
+            }),
+        )
+        .await;
+    let uri = format!("/reports/{}/resolve", id(&body, "report_id"));
+
+    assert_problem(
+        app.post(&uri, &market.customer.token, json!({})).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    // The action has to match the reported entity
+    assert_problem(
+        app.post(
+            &uri,
+            &market.admin.token,
+            json!({ "action": "DeactivateUser" }),
+        )
+        .await,
+        StatusCode::BAD_REQUEST,
+        "action_target_mismatch",
+    );
+
+    let (status, body) = app
+        .post(
+            &uri,
+            &market.admin.token,
+            json!({ "resolution": "Product removed", "action": "DeactivateProduct" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Resolved");
+    assert_eq!(body["action"], "DeactivateProduct");
+
+    // The product can't be ordered anymore
+    assert_problem(
+        app.post(
+            "/orders/register",
+            &market.customer.token,
+            json!({ "order_details": [{ "product_id": market.product_id, "amount": 1 }] }),
+        )
+        .await,
+        StatusCode::BAD_REQUEST,
+        "product_inactive",
+    );
+
+    assert_problem(
+        app.post(&uri, &market.admin.token, json!({})).await,
+        StatusCode::CONFLICT,
+        "report_closed",
+    );
+}
+
+#[sqlx::test]
+async fn dismiss_report(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let admin = app.admin("admin").await;
+    let alice = app.user("alice").await;
+    let bob = app.user("bob").await;
+
+    let (_, body) = app
+        .post(
+            "/reports",
+            &alice.token,
+            json!({
+                "title": "Rude",
+                "description": "Bob was rude on the phone",
+                "target": "User",
+                "target_id": bob.id,
+            }),
+        )
+        .await;
+    let uri = format!("/reports/{}/dismiss", id(&body, "report_id"));
+
+    assert_problem(
+        app.post(&uri, &bob.token, json!({})).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app
+        .post(
+            &uri,
+            &admin.token,
+            json!({ "resolution": "Not actionable" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Dismissed");
+
+    assert_problem(
+        app.post(&uri, &admin.token, json!({})).await,
+        StatusCode::CONFLICT,
+        "report_closed",
+    );
+}
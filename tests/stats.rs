@@ -0,0 +1,88 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TestApp, assert_problem};
+use sqlx::MySqlPool;
+
+#[sqlx::test]
+async fn stats_are_public(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    app.user("alice").await;
+    app.user("bob").await;
+
+    let (status, body) = app.request(Method::GET, "/stats", None, None).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["total_accounts"], 2);
+    assert_eq!(body["active_accounts"], 2);
+    assert_eq!(body["total_trips"], 0);
+    // The series is only included when asked for
+    assert!(body.get("series").is_none());
+}
+
+#[sqlx::test]
+async fn stats_follow_trip_transitions(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+
+    let (status, body) = app
+        .post_empty(
+            &format!("/trips/{}/cancel", trip_id),
+            &market.customer.token,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (_, body) = app.request(Method::GET, "/stats", None, None).await;
+    assert_eq!(body["total_trips"], 1);
+    assert_eq!(body["today_trips"], 1);
+    assert_eq!(body["cancellation_rate"], 1.0);
+
+    // A second trip for the same order is counted next to the canceled one
+    app.trip(&market, order_id).await;
+
+    let (_, body) = app.request(Method::GET, "/stats", None, None).await;
+    assert_eq!(body["total_trips"], 2);
+    assert_eq!(body["cancellation_rate"], 0.5);
+}
+
+#[sqlx::test]
+async fn stats_series(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    app.trip(&market, order_id).await;
+
+    let (status, body) = app
+        .request(Method::GET, "/stats?granularity=day", None, None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["total_trips"], 1);
+
+    // The buckets count the new accounts and the trip
+    let series = body["series"].as_array().unwrap();
+    let total = |field: &str| -> i64 {
+        series
+            .iter()
+            .map(|bucket| bucket[field].as_i64().unwrap())
+            .sum()
+    };
+    assert_eq!(total("trips"), 1);
+    assert_eq!(total("new_accounts"), 4);
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/stats?from=2026-02-01&to=2026-01-01",
+            None,
+            None,
+        )
+        .await;
+    assert_problem(
+        (status, body.clone()),
+        StatusCode::BAD_REQUEST,
+        "validation_failed",
+    );
+    assert_eq!(body["errors"][0]["code"], "invalid_range");
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, assert_problem, id};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+#[sqlx::test]
+async fn record_and_list_telemetry(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+
+    let uri = format!("/trips/{}/telemetry", trip_id);
+
+    let (status, body) = app
+        .post(
+            &uri,
+            &market.operator.token,
+            json!({
+                "latitude": -34.6280,
+                "longitude": -58.3960,
+                "altitude": 80.0,
+                "battery": 95.0,
+                "timestamp": "2026-01-01T10:00:00",
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "trip_id"), trip_id);
+    assert_eq!(body["real_distance"], 0.0);
+
+    let (status, body) = app
+        .post(
+            &uri,
+            &market.operator.token,
+            json!({
+                "latitude": -34.6160,
+                "longitude": -58.3890,
+                "altitude": 90.0,
+                "battery": 90.0,
+                "timestamp": "2026-01-01T10:02:00",
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    // The second reading adds the distance flown since the first one
+    assert!(body["real_distance"].as_f64().unwrap() > 1.0, "{}", body);
+
+    // The customer and the business owner can follow the flight
+    for user in [&market.customer, &market.owner] {
+        let (status, body) = app.get(&uri, &user.token).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        assert_eq!(body.as_array().unwrap().len(), 2);
+        assert_eq!(body[0]["battery"], 95.0);
+    }
+}
+
+#[sqlx::test]
+async fn telemetry_requires_drone_owner(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+    let other = app.user("other").await;
+
+    let uri = format!("/trips/{}/telemetry", trip_id);
+    let reading =
+        json!({ "latitude": -34.62, "longitude": -58.39, "altitude": 80.0, "battery": 95.0 });
+
+    assert_problem(
+        app.post(&uri, &market.customer.token, reading).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+    assert_problem(
+        app.get(&uri, &other.token).await,
+        StatusCode::FORBIDDEN,
+        "trip_access_denied",
+    );
+}
+
+#[sqlx::test]
+async fn telemetry_requires_a_trip_in_flight(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+
+    let (status, _) = app
+        .post_empty(
+            &format!("/trips/{}/cancel", trip_id),
+            &market.customer.token,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_problem(
+        app.post(
+            &format!("/trips/{}/telemetry", trip_id),
+            &market.operator.token,
+            json!({ "latitude": -34.62, "longitude": -58.39, "altitude": 80.0, "battery": 95.0 }),
+        )
+        .await,
+        StatusCode::CONFLICT,
+        "trip_not_in_flight",
+    );
+}
+
+#[sqlx::test]
+async fn telemetry_validates_the_reading(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+
+    let (status, body) = app
+        .post(
+            &format!("/trips/{}/telemetry", trip_id),
+            &market.operator.token,
+            json!({ "latitude": -34.62, "longitude": -58.39, "altitude": 80.0, "battery": 120.0 }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    assert_eq!(body["errors"][0]["field"], "battery");
+}
@@ -0,0 +1,426 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::{Method, StatusCode, header};
+use common::{DRONE_LATITUDE, DRONE_LONGITUDE, TestApp, assert_problem, id, trip_request};
+use http_body_util::BodyExt;
+use serde_json::json;
+use sqlx::MySqlPool;
+
+#[sqlx::test]
+async fn register_trip(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+
+    let (status, body) = app
+        .post(
+            "/trips/register",
+            &market.operator.token,
+            trip_request(&market, order_id),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Requested");
+    assert_eq!(id(&body, "drone_id"), market.drone_id);
+    // The business is about 3 km away from the take-off point
+    let distance = body["distance"].as_f64().unwrap();
+    assert!((2.0..4.0).contains(&distance), "distance {}", distance);
+    assert!(body["est_time"].as_f64().unwrap() > 0.0);
+    let trip_id = id(&body, "trip_id");
+
+    // Only one trip at a time per order
+    assert_problem(
+        app.post(
+            "/trips/register",
+            &market.operator.token,
+            trip_request(&market, order_id),
+        )
+        .await,
+        StatusCode::CONFLICT,
+        "trip_in_progress",
+    );
+
+    // The trip is reachable from its order
+    let (status, body) = app
+        .get(
+            &format!("/orders/{}/trip", order_id),
+            &market.customer.token,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "id"), trip_id);
+}
+
+#[sqlx::test]
+async fn concurrent_registrations_create_one_trip(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+
+    let register = || {
+        app.post(
+            "/trips/register",
+            &market.operator.token,
+            trip_request(&market, order_id),
+        )
+    };
+    let (first, second) = tokio::join!(register(), register());
+
+    let mut statuses = [first.0, second.0];
+    statuses.sort();
+    assert_eq!(statuses, [StatusCode::OK, StatusCode::CONFLICT]);
+}
+
+#[sqlx::test]
+async fn register_trip_requires_drone_owner(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+
+    assert_problem(
+        app.post(
+            "/trips/register",
+            &market.customer.token,
+            trip_request(&market, order_id),
+        )
+        .await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    // Operators can only fly their own drones
+    let rival = app.user("rival").await;
+    app.drone(&rival, 2).await;
+    assert_problem(
+        app.post(
+            "/trips/register",
+            &rival.token,
+            trip_request(&market, order_id),
+        )
+        .await,
+        StatusCode::FORBIDDEN,
+        "not_drone_owner",
+    );
+}
+
+#[sqlx::test]
+async fn register_trip_requires_an_approved_order(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.order(&market).await;
+
+    assert_problem(
+        app.post(
+            "/trips/register",
+            &market.operator.token,
+            trip_request(&market, order_id),
+        )
+        .await,
+        StatusCode::BAD_REQUEST,
+        "order_not_ready",
+    );
+}
+
+#[sqlx::test]
+async fn get_trip_requires_access(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+    let other = app.user("other").await;
+
+    let uri = format!("/trips/{}", trip_id);
+
+    assert_problem(
+        app.get(&uri, &other.token).await,
+        StatusCode::FORBIDDEN,
+        "trip_access_denied",
+    );
+    assert_problem(
+        app.get(&format!("/orders/{}/trip", order_id), &other.token)
+            .await,
+        StatusCode::FORBIDDEN,
+        "trip_access_denied",
+    );
+
+    // The operator, the customer and the business owner can follow the trip
+    for user in [&market.operator, &market.customer, &market.owner] {
+        let (status, body) = app.get(&uri, &user.token).await;
+        assert_eq!(status, StatusCode::OK, "{}: {}", user.username, body);
+        assert_eq!(id(&body, "order_id"), order_id);
+        assert_eq!(body["from_location"]["latitude"], DRONE_LATITUDE);
+        assert_eq!(body["from_location"]["longitude"], DRONE_LONGITUDE);
+    }
+}
+
+#[sqlx::test]
+async fn deliver_and_finish_trip(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+
+    let deliver = format!("/trips/{}/deliver", trip_id);
+    let finish = format!("/trips/{}/finish", trip_id);
+
+    // Only the drone operator moves the trip forward
+    assert_problem(
+        app.post(&deliver, &market.customer.token, json!({})).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    // A trip can't finish before the package is delivered
+    assert_problem(
+        app.post(&finish, &market.operator.token, json!({})).await,
+        StatusCode::CONFLICT,
+        "invalid_transition",
+    );
+
+    let (status, body) = app
+        .post(
+            &deliver,
+            &market.operator.token,
+            json!({ "packing_time": 4.5, "battery_init": 95.0, "battery_arrival": 80.0 }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Delivered");
+
+    assert_problem(
+        app.post(&finish, &market.customer.token, json!({})).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app
+        .post(
+            &finish,
+            &market.operator.token,
+            json!({ "battery_end": 65.0, "real_distance": 6.4 }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Finished");
+
+    // The order is finished with its trip
+    let (_, body) = app
+        .get(&format!("/orders/{}", order_id), &market.customer.token)
+        .await;
+    assert_eq!(body["state"], "Finished");
+
+    // And it counts towards the drone metrics and the business analytics
+    let (_, body) = app
+        .get(
+            &format!("/drones/{}/metrics", market.drone_id),
+            &market.operator.token,
+        )
+        .await;
+    assert_eq!(body["total_flights"], 1);
+    assert_eq!(body["total_distance"], 6.4);
+
+    let (_, body) = app
+        .get(
+            &format!("/business/{}/analytics", market.business_id),
+            &market.owner.token,
+        )
+        .await;
+    assert_eq!(body["order_count"], 1);
+    assert_eq!(body["revenue"], 25.0);
+
+    // Sell the product again after renaming it
+    let (status, body) = app
+        .put(
+            &format!("/product/{}", market.product_id),
+            &market.owner.token,
+            json!({ "name": "Facturas" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let renamed_order_id = app.approved_order(&market).await;
+    let renamed_trip_id = app.trip(&market, renamed_order_id).await;
+    for step in ["deliver", "finish"] {
+        let (status, body) = app
+            .post(
+                &format!("/trips/{}/{}", renamed_trip_id, step),
+                &market.operator.token,
+                json!({}),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+    }
+
+    // The rename doesn't rewrite the past order
+    let (_, body) = app
+        .get(&format!("/orders/{}", order_id), &market.customer.token)
+        .await;
+    assert_eq!(body["order_details"][0]["product_name"], "Medialunas");
+
+    // But both sales are ranked as one product
+    let (_, body) = app
+        .get(
+            &format!("/business/{}/analytics", market.business_id),
+            &market.owner.token,
+        )
+        .await;
+    assert_eq!(body["order_count"], 2);
+    let top_products = body["top_products"].as_array().unwrap();
+    assert_eq!(top_products.len(), 1, "{}", body);
+    assert_eq!(top_products[0]["product_id"], market.product_id);
+    assert_eq!(top_products[0]["name"], "Facturas");
+    assert_eq!(top_products[0]["units_sold"], 4);
+}
+
+#[sqlx::test]
+async fn fail_trip(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+
+    let uri = format!("/trips/{}/fail", trip_id);
+
+    assert_problem(
+        app.post_empty(&uri, &market.customer.token).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app.post_empty(&uri, &market.operator.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Unfinished");
+
+    let (_, body) = app
+        .get(&format!("/orders/{}", order_id), &market.customer.token)
+        .await;
+    assert_eq!(body["state"], "Uncomplete");
+
+    // A failed trip is final
+    assert_problem(
+        app.post_empty(&uri, &market.operator.token).await,
+        StatusCode::CONFLICT,
+        "invalid_transition",
+    );
+}
+
+#[sqlx::test]
+async fn fail_trip_after_delivery_finishes_the_order(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+
+    let (status, body) = app
+        .post(
+            &format!("/trips/{}/deliver", trip_id),
+            &market.operator.token,
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, body) = app
+        .post_empty(&format!("/trips/{}/fail", trip_id), &market.operator.token)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Unfinished");
+
+    // The package was handed over before the drone failed on its way back
+    let (_, body) = app
+        .get(&format!("/orders/{}", order_id), &market.customer.token)
+        .await;
+    assert_eq!(body["state"], "Finished");
+}
+
+#[sqlx::test]
+async fn cancel_trip(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+
+    let uri = format!("/trips/{}/cancel", trip_id);
+
+    // The business owner can follow the trip but not cancel it
+    assert_problem(
+        app.post_empty(&uri, &market.owner.token).await,
+        StatusCode::FORBIDDEN,
+        "trip_access_denied",
+    );
+
+    let (status, body) = app.post_empty(&uri, &market.customer.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["state"], "Canceled");
+
+    assert_problem(
+        app.post_empty(&uri, &market.operator.token).await,
+        StatusCode::CONFLICT,
+        "invalid_transition",
+    );
+
+    // The customer's stats count the canceled trip
+    let (_, body) = app
+        .get(
+            &format!("/users/{}/stats", market.customer.id),
+            &market.customer.token,
+        )
+        .await;
+    assert_eq!(body["trips"], 1);
+    assert_eq!(body["canceled_trips"], 1);
+
+    // The order is still waiting for a drone, so another trip can be dispatched
+    let (_, body) = app
+        .get(&format!("/orders/{}", order_id), &market.customer.token)
+        .await;
+    assert_eq!(body["state"], "Requested");
+    app.trip(&market, order_id).await;
+}
+
+#[sqlx::test]
+async fn trip_events_stream_state_changes(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+    let trip_id = app.trip(&market, order_id).await;
+    let other = app.user("other").await;
+
+    let uri = format!("/trips/{}/events", trip_id);
+
+    assert_problem(
+        app.get(&uri, &other.token).await,
+        StatusCode::FORBIDDEN,
+        "trip_access_denied",
+    );
+
+    let response = app
+        .send(Method::GET, &uri, Some(&market.customer.token), None)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/event-stream"
+    );
+
+    let (status, _) = app
+        .post(
+            &format!("/trips/{}/deliver", trip_id),
+            &market.operator.token,
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The stream never ends, so read a single event
+    let mut body = response.into_body();
+    let frame = tokio::time::timeout(Duration::from_secs(5), body.frame())
+        .await
+        .expect("No event received")
+        .unwrap()
+        .unwrap();
+    let event = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+    assert!(event.contains("event: state_changed"), "{}", event);
+    assert!(event.contains(r#""state":"Delivered""#), "{}", event);
+}
@@ -0,0 +1,441 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TestApp, assert_problem, id};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+/// Every route that requires a JWT, with a placeholder ID where the path needs one
+const PROTECTED_ROUTES: &[(&str, &str)] = &[
+    ("POST", "/auth/logout"),
+    ("PUT", "/users/1"),
+    ("DELETE", "/users/1"),
+    ("GET", "/users/1/stats"),
+    ("POST", "/business/register"),
+    ("GET", "/business/list"),
+    ("GET", "/business/pending"),
+    ("PUT", "/business/1"),
+    ("DELETE", "/business/1"),
+    ("POST", "/business/1/location"),
+    ("GET", "/business/1/analytics"),
+    ("POST", "/business/1/verify"),
+    ("POST", "/business/1/reject"),
+    ("POST", "/business/1/employees"),
+    ("GET", "/business/1/employees"),
+    ("DELETE", "/business/1/employees/1"),
+    ("GET", "/business/1/orders"),
+    ("POST", "/product/register"),
+    ("PUT", "/product/1"),
+    ("DELETE", "/product/1"),
+    ("GET", "/product/business/1"),
+    ("POST", "/drones/register"),
+    ("GET", "/drones/list"),
+    ("DELETE", "/drones/1"),
+    ("GET", "/drones/1/metrics"),
+    ("GET", "/drones/1/trips"),
+    ("POST", "/orders/register"),
+    ("GET", "/orders"),
+    ("GET", "/orders/1"),
+    ("POST", "/orders/1/approve"),
+    ("POST", "/orders/1/reject"),
+    ("POST", "/orders/1/cancel"),
+    ("GET", "/orders/1/trip"),
+    ("POST", "/trips/register"),
+    ("GET", "/trips/1"),
+    ("POST", "/trips/1/deliver"),
+    ("POST", "/trips/1/finish"),
+    ("POST", "/trips/1/cancel"),
+    ("POST", "/trips/1/fail"),
+    ("GET", "/trips/1/events"),
+    ("POST", "/trips/1/telemetry"),
+    ("GET", "/trips/1/telemetry"),
+    ("POST", "/zones/register"),
+    ("GET", "/zones/list"),
+    ("PUT", "/zones/1"),
+    ("DELETE", "/zones/1"),
+    ("POST", "/reports"),
+    ("GET", "/reports"),
+    ("POST", "/reports/1/resolve"),
+    ("POST", "/reports/1/dismiss"),
+];
+
+#[sqlx::test]
+async fn protected_routes_require_a_token(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+
+    for (method, uri) in PROTECTED_ROUTES {
+        let method = Method::from_bytes(method.as_bytes()).unwrap();
+        let (status, body) = app.request(method.clone(), uri, None, None).await;
+        assert_eq!(
+            status,
+            StatusCode::UNAUTHORIZED,
+            "{} {} without a token: {}",
+            method,
+            uri,
+            body
+        );
+        assert_eq!(body["code"], "missing_token", "{} {}", method, uri);
+    }
+}
+
+#[sqlx::test]
+async fn protected_routes_reject_an_invalid_token(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+
+    assert_problem(
+        app.get("/drones/list", "not-a-jwt").await,
+        StatusCode::UNAUTHORIZED,
+        "invalid_token",
+    );
+}
+
+#[sqlx::test]
+async fn register_and_login(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+
+    let user = app.user("alice").await;
+    assert!(user.id > 0);
+    assert!(!user.token.is_empty());
+
+    // The username is unique
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/auth/register",
+            None,
+            Some(json!({
+                "username": "alice",
+                "name": "Alice",
+                "lastname": "Again",
+                "phone": 1155550001,
+                "email": "alice2@example.com",
+                "password": "another-password",
+            })),
+        )
+        .await;
+    assert_problem((status, body), StatusCode::CONFLICT, "username_taken");
+
+    // A wrong password is rejected
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/auth/login",
+            None,
+            Some(json!({ "username": "alice", "password": "wrong-password" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn register_reports_invalid_fields(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/auth/register",
+            None,
+            Some(json!({
+                "username": "al",
+                "name": "Alice",
+                "lastname": "Liddell",
+                "phone": 1155550000,
+                "email": "not-an-email",
+                "password": "short",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    assert_eq!(body["code"], "validation_failed");
+
+    let fields: Vec<&str> = body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["field"].as_str().unwrap())
+        .collect();
+    assert!(fields.contains(&"username"));
+    assert!(fields.contains(&"email"));
+    assert!(fields.contains(&"password"));
+}
+
+#[sqlx::test]
+async fn refresh_rotates_the_token(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let user = app.user("alice").await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/auth/refresh",
+            None,
+            Some(json!({ "refresh_token": user.refresh_token })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "user_id"), user.id);
+    assert_ne!(body["refresh_token"], user.refresh_token.as_str());
+
+    // The old refresh token can't be used twice
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/auth/refresh",
+            None,
+            Some(json!({ "refresh_token": user.refresh_token })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn reusing_a_rotated_token_revokes_its_family(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let laptop = app.user("alice").await;
+    let phone = app.login("alice").await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/auth/refresh",
+            None,
+            Some(json!({ "refresh_token": laptop.refresh_token })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let rotated = body["refresh_token"].as_str().unwrap().to_string();
+
+    // The rotated token is sent again, as a thief would
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/auth/refresh",
+            None,
+            Some(json!({ "refresh_token": laptop.refresh_token })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // The token issued by the rotation is revoked with it
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/auth/refresh",
+            None,
+            Some(json!({ "refresh_token": rotated })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // The other session is still logged in
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/auth/refresh",
+            None,
+            Some(json!({ "refresh_token": phone.refresh_token })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+}
+
+#[sqlx::test]
+async fn reusing_a_logged_out_token_is_only_rejected(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let laptop = app.user("alice").await;
+    let phone = app.login("alice").await;
+
+    let (status, body) = app
+        .post(
+            "/auth/logout",
+            &laptop.token,
+            json!({ "refresh_token": laptop.refresh_token }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/auth/refresh",
+            None,
+            Some(json!({ "refresh_token": laptop.refresh_token })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // A logout isn't a leak, nothing else is revoked
+    let revoked: Vec<String> = sqlx::query_scalar(
+        "SELECT revoked_reason FROM refresh_tokens WHERE user_id = ? AND revoked_at IS NOT NULL",
+    )
+    .bind(laptop.id)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(revoked, ["Logout"]);
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/auth/refresh",
+            None,
+            Some(json!({ "refresh_token": phone.refresh_token })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+}
+
+#[sqlx::test]
+async fn logout_revokes_the_tokens(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let user = app.user("alice").await;
+
+    let (status, body) = app
+        .post(
+            "/auth/logout",
+            &user.token,
+            json!({ "refresh_token": user.refresh_token }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    // The access token is on the denylist
+    let (status, _) = app.get("/drones/list", &user.token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // And the refresh token is revoked
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/auth/refresh",
+            None,
+            Some(json!({ "refresh_token": user.refresh_token })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn logout_applies_to_every_instance(pool: MySqlPool) {
+    // Two instances of the backend sharing the database
+    let first = TestApp::new(pool.clone());
+    let second = TestApp::new(pool);
+    let user = first.user("alice").await;
+
+    let (status, body) = second.get("/drones/list", &user.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, body) = first
+        .post(
+            "/auth/logout",
+            &user.token,
+            json!({ "refresh_token": user.refresh_token }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    // The other instance rejects the access token right away
+    assert_problem(
+        second.get("/drones/list", &user.token).await,
+        StatusCode::UNAUTHORIZED,
+        "invalid_token",
+    );
+}
+
+#[sqlx::test]
+async fn jwks_is_public(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+
+    let (status, body) = app
+        .request(Method::GET, "/.well-known/jwks.json", None, None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    // HS256 has no public keys to publish
+    assert_eq!(body, json!({ "keys": [] }));
+}
+
+#[sqlx::test]
+async fn update_user_is_self_or_admin(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let admin = app.admin("admin").await;
+    let alice = app.user("alice").await;
+    let bob = app.user("bob").await;
+
+    let uri = format!("/users/{}", alice.id);
+
+    assert_problem(
+        app.put(&uri, &bob.token, json!({ "name": "Mallory" }))
+            .await,
+        StatusCode::FORBIDDEN,
+        "not_self_or_admin",
+    );
+
+    let (status, body) = app
+        .put(&uri, &alice.token, json!({ "name": "Alicia" }))
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "user_id"), alice.id);
+
+    let (status, body) = app
+        .put(&uri, &admin.token, json!({ "lastname": "Doe" }))
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+}
+
+#[sqlx::test]
+async fn delete_user_requires_admin(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let admin = app.admin("admin").await;
+    let alice = app.user("alice").await;
+    let bob = app.user("bob").await;
+
+    let uri = format!("/users/{}", alice.id);
+
+    assert_problem(
+        app.delete(&uri, &bob.token).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app.delete(&uri, &admin.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "user_id"), alice.id);
+
+    // A deactivated user can't log in again
+    assert_problem(
+        app.request(
+            Method::POST,
+            "/auth/login",
+            None,
+            Some(json!({ "username": "alice", "password": "correct-horse-battery" })),
+        )
+        .await,
+        StatusCode::FORBIDDEN,
+        "user_inactive",
+    );
+}
+
+#[sqlx::test]
+async fn user_stats_are_self_or_admin(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let admin = app.admin("admin").await;
+    let alice = app.user("alice").await;
+    let bob = app.user("bob").await;
+
+    let uri = format!("/users/{}/stats", alice.id);
+
+    assert_problem(
+        app.get(&uri, &bob.token).await,
+        StatusCode::FORBIDDEN,
+        "not_self_or_admin",
+    );
+
+    let (status, body) = app.get(&uri, &alice.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(id(&body, "user_id"), alice.id);
+    assert_eq!(body["trips"], 0);
+
+    let (status, _) = app.get(&uri, &admin.token).await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, assert_problem, id, trip_request};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+#[sqlx::test]
+async fn register_zone_requires_admin(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let admin = app.admin("admin").await;
+    let operator = app.user("operator").await;
+
+    let zone = json!({
+        "description": "Stadium, match day",
+        "radius": 1.5,
+        "name": "Stadium",
+        "latitude": -34.6355,
+        "longitude": -58.3649,
+    });
+
+    assert_problem(
+        app.post("/zones/register", &operator.token, zone.clone())
+            .await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app.post("/zones/register", &admin.token, zone).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let zone_id = id(&body, "zone_id");
+
+    // Every authenticated user can plan around the zones
+    let (status, body) = app.get("/zones/list", &operator.token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(id(&body[0], "id"), zone_id);
+    assert_eq!(body[0]["radius"], 1.5);
+}
+
+#[sqlx::test]
+async fn register_zone_validates_the_window(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let admin = app.admin("admin").await;
+
+    assert_problem(
+        app.post(
+            "/zones/register",
+            &admin.token,
+            json!({
+                "description": "Backwards",
+                "since": "2026-02-01T00:00:00",
+                "to": "2026-01-01T00:00:00",
+                "radius": 1.0,
+                "latitude": -34.6,
+                "longitude": -58.4,
+            }),
+        )
+        .await,
+        StatusCode::BAD_REQUEST,
+        "invalid_window",
+    );
+}
+
+#[sqlx::test]
+async fn update_and_delete_zone_require_admin(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let admin = app.admin("admin").await;
+    let operator = app.user("operator").await;
+
+    let (_, body) = app
+        .post(
+            "/zones/register",
+            &admin.token,
+            json!({ "description": "Airport", "radius": 5.0, "latitude": -34.5592, "longitude": -58.4156 }),
+        )
+        .await;
+    let uri = format!("/zones/{}", id(&body, "zone_id"));
+
+    assert_problem(
+        app.put(&uri, &operator.token, json!({ "radius": 0.5 }))
+            .await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+    assert_problem(
+        app.delete(&uri, &operator.token).await,
+        StatusCode::FORBIDDEN,
+        "missing_role",
+    );
+
+    let (status, body) = app.put(&uri, &admin.token, json!({ "radius": 6.0 })).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let (_, body) = app.get("/zones/list", &operator.token).await;
+    assert_eq!(body[0]["radius"], 6.0);
+
+    let (status, body) = app.delete(&uri, &admin.token).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let (_, body) = app.get("/zones/list", &operator.token).await;
+    assert!(body.as_array().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn zones_block_trips_across_them(pool: MySqlPool) {
+    let app = TestApp::new(pool);
+    let market = app.marketplace().await;
+    let order_id = app.approved_order(&market).await;
+
+    // A zone right between the take-off point and the business
+    let (status, body) = app
+        .post(
+            "/zones/register",
+            &market.admin.token,
+            json!({
+                "description": "Demonstration",
+                "radius": 0.5,
+                "latitude": -34.6160,
+                "longitude": -58.3890,
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let zone_id = id(&body, "zone_id");
+
+    let (status, body) = app
+        .post(
+            "/trips/register",
+            &market.operator.token,
+            trip_request(&market, order_id),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+    assert_eq!(body["code"], "route_blocked");
+    assert_eq!(id(&body, "zone_id"), zone_id);
+
+    // Zones that ended before the flight don't block it
+    let (status, _) = app
+        .put(
+            &format!("/zones/{}", zone_id),
+            &market.admin.token,
+            json!({ "since": "2025-01-01T00:00:00", "to": "2025-01-02T00:00:00" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app
+        .post(
+            "/trips/register",
+            &market.operator.token,
+            trip_request(&market, order_id),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+}